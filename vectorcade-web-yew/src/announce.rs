@@ -0,0 +1,43 @@
+//! Screen reader announcements.
+//!
+//! Short messages (game switched, score, game over) are written into a
+//! visually hidden `aria-live="polite"` element that the `App` renders.
+//! Announcements closer together than `MIN_GAP_MS` are coalesced: only the
+//! latest is read once the gap has passed, so a burst doesn't queue up a
+//! backlog in the reader.
+
+use wasm_bindgen::prelude::*;
+
+use crate::GAME_STATE;
+
+/// Minimum time between announcements (ms).
+const MIN_GAP_MS: f64 = 1500.0;
+
+#[derive(Default)]
+pub struct Announcer {
+    pending: Option<String>,
+    last_at: Option<f64>,
+}
+
+impl Announcer {
+    /// Queue `msg`, replacing anything not yet announced.
+    pub fn announce(&mut self, msg: impl Into<String>) {
+        self.pending = Some(msg.into());
+    }
+
+    /// The message to announce at `now` (ms), if one is due.
+    pub fn poll(&mut self, now: f64) -> Option<String> {
+        if self.last_at.is_some_and(|at| now - at < MIN_GAP_MS) {
+            return None;
+        }
+        let msg = self.pending.take()?;
+        self.last_at = Some(now);
+        Some(msg)
+    }
+}
+
+/// Have screen readers announce `text` (throttled; see module docs).
+#[wasm_bindgen]
+pub fn announce(text: &str) {
+    GAME_STATE.with(|state| state.borrow_mut().announcer.announce(text));
+}
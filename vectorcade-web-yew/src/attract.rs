@@ -0,0 +1,118 @@
+//! Attract mode for kiosks.
+//!
+//! After `IDLE_S` without input a kiosk starts showcasing games, switching
+//! to a weighted random pick every `DWELL_S`. Weights come from
+//! `GameProfile::attract_weight`; a weight of 0 keeps a game out of the
+//! showcase. Any input stops cycling on whatever game is up.
+
+use vectorcade_shared::Xorshift64;
+
+/// Seconds without input before the showcase starts.
+pub const IDLE_S: f64 = 60.0;
+/// Seconds each game is shown.
+pub const DWELL_S: f64 = 30.0;
+
+pub struct Attract {
+    last_input: f64,
+    next_switch: f64,
+    /// Apart from the game RNG, so the showcase never changes a run.
+    rng: Xorshift64,
+}
+
+impl Default for Attract {
+    fn default() -> Self {
+        Self {
+            last_input: 0.0,
+            next_switch: 0.0,
+            rng: Xorshift64::new(1),
+        }
+    }
+}
+
+impl Attract {
+    /// Note input at `now` (ms).
+    pub fn input(&mut self, now: f64) {
+        self.last_input = now;
+        self.next_switch = 0.0;
+    }
+
+    /// Whether a switch is due at `now` (ms).
+    pub fn due(&mut self, now: f64) -> bool {
+        if self.last_input == 0.0 {
+            self.last_input = now;
+            self.rng = Xorshift64::new((now as u64).max(1));
+        }
+        if now - self.last_input < IDLE_S * 1000.0 || now < self.next_switch {
+            return false;
+        }
+        self.next_switch = now + DWELL_S * 1000.0;
+        true
+    }
+
+    /// Weighted random game index, avoiding `current` when anything else
+    /// is eligible. `None` if every weight is zero.
+    pub fn pick(&mut self, current: usize, weights: &[f32]) -> Option<usize> {
+        let eligible = |i: usize| weights[i] > 0.0 && (i != current || only(weights, current));
+        let total: f32 = (0..weights.len())
+            .filter(|&i| eligible(i))
+            .map(|i| weights[i])
+            .sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let mut r = self.rng.next_f32() * total;
+        let mut last = None;
+        for i in (0..weights.len()).filter(|&i| eligible(i)) {
+            if r < weights[i] {
+                return Some(i);
+            }
+            r -= weights[i];
+            last = Some(i);
+        }
+        // Rounding left `r` just past the end.
+        last
+    }
+}
+
+/// `current` is the only game with a positive weight.
+fn only(weights: &[f32], current: usize) -> bool {
+    weights
+        .iter()
+        .enumerate()
+        .all(|(i, w)| i == current || *w <= 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_weight_is_never_picked() {
+        let mut attract = Attract::default();
+        let weights = [1.0, 0.0, 2.0, 0.0];
+        for _ in 0..1000 {
+            let pick = attract.pick(0, &weights);
+            assert_eq!(pick, Some(2));
+        }
+        for _ in 0..1000 {
+            let pick = attract.pick(1, &weights).unwrap();
+            assert!(weights[pick] > 0.0);
+        }
+        assert_eq!(attract.pick(0, &[0.0, 0.0]), None);
+        // The current game is kept when it is the only eligible one.
+        assert_eq!(attract.pick(1, &[0.0, 1.0, 0.0]), Some(1));
+    }
+
+    #[test]
+    fn picks_follow_weights() {
+        let mut attract = Attract::default();
+        let weights = [1.0, 0.0, 3.0, 0.0];
+        let mut counts = [0u32; 4];
+        for _ in 0..10_000 {
+            counts[attract.pick(3, &weights).unwrap()] += 1;
+        }
+        assert_eq!(counts[1] + counts[3], 0);
+        let share = counts[2] as f32 / 10_000.0;
+        assert!((share - 0.75).abs() < 0.03, "share {}", share);
+    }
+}
@@ -0,0 +1,182 @@
+//! WebAudio output with named mixing buses and per-sound throttling.
+//!
+//! Every sound plays through a bus (`sfx` or `music`) with its own gain.
+//! Repeats of the same named sound closer together than `THROTTLE_MS` are
+//! dropped, and new sounds are dropped while `MAX_VOICES` are already
+//! playing, so rapid fire stays clean instead of piling up oscillators.
+//!
+//! While the page is hidden the context is suspended and sounds are
+//! dropped rather than queued, so nothing bursts out on return.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+use wasm_bindgen::prelude::*;
+use web_sys::{AudioContext, GainNode, OscillatorType};
+
+use crate::logging;
+use crate::settings::Settings;
+
+/// Minimum gap between two plays of the same named sound (ms).
+const THROTTLE_MS: f64 = 16.0;
+/// Most oscillators allowed at once.
+const MAX_VOICES: usize = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bus {
+    Sfx,
+    Music,
+}
+
+impl Bus {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "sfx" => Some(Self::Sfx),
+            "music" => Some(Self::Music),
+            _ => None,
+        }
+    }
+}
+
+struct Mixer {
+    ctx: AudioContext,
+    sfx: GainNode,
+    music: GainNode,
+    /// Last start time per sound name (ms, audio clock).
+    last_played: HashMap<String, f64>,
+    /// End times of voices still sounding (ms, audio clock).
+    voices: Vec<f64>,
+}
+
+impl Mixer {
+    fn new() -> Result<Self, JsValue> {
+        let ctx = AudioContext::new()?;
+        let sfx = ctx.create_gain()?;
+        let music = ctx.create_gain()?;
+        sfx.connect_with_audio_node(&ctx.destination())?;
+        music.connect_with_audio_node(&ctx.destination())?;
+        let (sfx_volume, music_volume) = VOLUMES.with(Cell::get);
+        sfx.gain().set_value(sfx_volume);
+        music.gain().set_value(music_volume);
+        Ok(Self {
+            ctx,
+            sfx,
+            music,
+            last_played: HashMap::new(),
+            voices: Vec::new(),
+        })
+    }
+
+    fn bus(&self, bus: Bus) -> &GainNode {
+        match bus {
+            Bus::Sfx => &self.sfx,
+            Bus::Music => &self.music,
+        }
+    }
+
+    fn play_tone(
+        &mut self,
+        bus: Bus,
+        name: &str,
+        freq_hz: f32,
+        duration_ms: f64,
+    ) -> Result<(), JsValue> {
+        let now_ms = self.ctx.current_time() * 1000.0;
+        if self
+            .last_played
+            .get(name)
+            .is_some_and(|last| now_ms - last < THROTTLE_MS)
+        {
+            return Ok(());
+        }
+        self.voices.retain(|end| *end > now_ms);
+        if self.voices.len() >= MAX_VOICES {
+            return Ok(());
+        }
+        self.last_played.insert(name.to_string(), now_ms);
+        self.voices.push(now_ms + duration_ms);
+
+        let osc = self.ctx.create_oscillator()?;
+        osc.set_type(OscillatorType::Square);
+        osc.frequency().set_value(freq_hz);
+        osc.connect_with_audio_node(self.bus(bus))?;
+        osc.start()?;
+        osc.stop_with_when(self.ctx.current_time() + duration_ms / 1000.0)?;
+        Ok(())
+    }
+}
+
+thread_local! {
+    /// Created on first use, since browsers only allow audio after a gesture.
+    static MIXER: RefCell<Option<Mixer>> = const { RefCell::new(None) };
+    /// (sfx, music) bus gains.
+    static VOLUMES: Cell<(f32, f32)> = const { Cell::new((1.0, 1.0)) };
+    /// The page is hidden; sounds are dropped.
+    static SUSPENDED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Suspend audio while the page is hidden and resume it when visible.
+/// Independent of game pause.
+pub fn set_suspended(suspended: bool) {
+    if SUSPENDED.with(|s| s.replace(suspended)) == suspended {
+        return;
+    }
+    MIXER.with(|m| {
+        if let Some(mixer) = m.borrow().as_ref() {
+            let result = if suspended {
+                mixer.ctx.suspend()
+            } else {
+                mixer.ctx.resume()
+            };
+            if let Err(e) = result {
+                logging::debug(&format!("audio suspend/resume failed: {:?}", e));
+            }
+        }
+    });
+}
+
+/// Apply the bus volumes from `settings`.
+pub fn set_volumes(settings: &Settings) {
+    let volumes = (settings.sfx_volume, settings.music_volume);
+    VOLUMES.with(|v| v.set(volumes));
+    MIXER.with(|m| {
+        if let Some(mixer) = m.borrow().as_ref() {
+            mixer.sfx.gain().set_value(volumes.0);
+            mixer.music.gain().set_value(volumes.1);
+        }
+    });
+}
+
+/// Play a square-wave tone named `name` on `bus`, subject to throttling.
+pub fn play_tone(bus: Bus, name: &str, freq_hz: f32, duration_ms: f64) {
+    if SUSPENDED.with(Cell::get) {
+        return;
+    }
+    MIXER.with(|m| {
+        let mut slot = m.borrow_mut();
+        if slot.is_none() {
+            match Mixer::new() {
+                Ok(mixer) => *slot = Some(mixer),
+                Err(e) => {
+                    logging::warn(&format!("audio unavailable: {:?}", e));
+                    return;
+                }
+            }
+        }
+        if let Some(mixer) = slot.as_mut() {
+            if let Err(e) = mixer.play_tone(bus, name, freq_hz, duration_ms) {
+                logging::debug(&format!("tone {:?} failed: {:?}", name, e));
+            }
+        }
+    });
+}
+
+/// Play a tone on the `sfx` or `music` bus; intended for host pages and
+/// future game hooks.
+#[wasm_bindgen]
+pub fn play_sound(bus: &str, name: &str, freq_hz: f32, duration_ms: f64) {
+    match Bus::parse(bus) {
+        Some(bus) => play_tone(bus, name, freq_hz, duration_ms),
+        None => logging::warn(&format!("unknown audio bus {:?}", bus)),
+    }
+}
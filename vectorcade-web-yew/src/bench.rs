@@ -0,0 +1,126 @@
+//! Renderer stress test fed with synthetic draw commands.
+//!
+//! Enabled with `?bench=N`: game logic is bypassed and every frame draws `N`
+//! random lines and polylines. After a warmup the frame intervals are
+//! recorded, and a min/avg/p95 summary is logged when the run ends.
+//!
+//! `?bench_spread=S` scatters points over `[-S, S]` instead of the visible
+//! box, standing in for games with large wrapping worlds; compare runs with
+//! and without `?cull=0` to see what off-screen culling saves.
+
+use glam::Vec2;
+use vectorcade_shared::draw::DrawCmd;
+use vectorcade_shared::{Rgba, Xorshift64};
+
+use crate::{logging, overlay, query};
+
+/// Frames during this initial window are not measured (ms).
+const WARMUP_MS: f64 = 2_000.0;
+/// Length of the measured run after warmup (ms).
+const RUN_MS: f64 = 10_000.0;
+/// Upper bound on primitives per frame.
+const MAX_PRIMITIVES: usize = 200_000;
+
+pub struct Benchmark {
+    primitives: usize,
+    spread: f32,
+    culled: usize,
+    /// Apart from the game RNG; a fixed seed keeps runs comparable.
+    rng: Xorshift64,
+    start: Option<f64>,
+    last: Option<f64>,
+    samples: Vec<f64>,
+    finished: bool,
+}
+
+impl Benchmark {
+    /// Create a benchmark if `?bench=N` is present in the page URL.
+    pub fn from_url() -> Option<Self> {
+        let primitives = query::param("bench")?.parse::<usize>().ok()?;
+        logging::info(&format!("benchmark: {} primitives per frame", primitives));
+        Some(Self {
+            primitives: primitives.min(MAX_PRIMITIVES),
+            spread: query::param("bench_spread")
+                .and_then(|v| v.parse::<f32>().ok())
+                .unwrap_or(1.0),
+            culled: 0,
+            rng: Xorshift64::new(0x9e37_79b9_7f4a_7c15),
+            start: None,
+            last: None,
+            samples: Vec::new(),
+            finished: false,
+        })
+    }
+
+    /// Record timing for this frame and emit its synthetic commands.
+    pub fn frame(&mut self, now: f64, out: &mut Vec<DrawCmd>) {
+        let start = *self.start.get_or_insert(now);
+        let elapsed = now - start;
+        if let Some(last) = self.last {
+            if elapsed > WARMUP_MS && !self.finished {
+                self.samples.push(now - last);
+            }
+        }
+        self.last = Some(now);
+        if elapsed > WARMUP_MS + RUN_MS && !self.finished {
+            self.finished = true;
+            self.report();
+        }
+
+        out.push(DrawCmd::Clear {
+            color: Rgba(0.0, 0.0, 0.0, 1.0),
+        });
+        for i in 0..self.primitives {
+            let color = Rgba(
+                self.rng.next_f32(),
+                self.rng.next_f32(),
+                self.rng.next_f32(),
+                1.0,
+            );
+            if i % 2 == 0 {
+                let a = self.point();
+                let b = self.point();
+                overlay::line(out, a, b, color, 1.5);
+            } else {
+                let pts = (0..4).map(|_| self.point()).collect();
+                overlay::polyline(out, pts, false, color, 1.5);
+            }
+        }
+    }
+
+    /// Count primitives culled from the frame just emitted.
+    pub fn record_culled(&mut self, n: usize) {
+        if !self.finished && !self.samples.is_empty() {
+            self.culled += n;
+        }
+    }
+
+    fn report(&self) {
+        let mut sorted = self.samples.clone();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let Some(&min) = sorted.first() else {
+            logging::warn("benchmark: no frames recorded");
+            return;
+        };
+        let avg = sorted.iter().sum::<f64>() / sorted.len() as f64;
+        let p95 = sorted[(sorted.len() * 95 / 100).min(sorted.len() - 1)];
+        logging::info(&format!(
+            "benchmark: {} primitives, {} frames, frame ms min {:.2} avg {:.2} p95 {:.2} ({:.1} fps), {:.0} culled/frame",
+            self.primitives,
+            sorted.len(),
+            min,
+            avg,
+            p95,
+            1000.0 / avg,
+            self.culled as f64 / sorted.len() as f64
+        ));
+    }
+
+    fn point(&mut self) -> Vec2 {
+        let spread = self.spread;
+        Vec2::new(
+            self.rng.next_f32() * 2.0 - 1.0,
+            self.rng.next_f32() * 2.0 - 1.0,
+        ) * spread
+    }
+}
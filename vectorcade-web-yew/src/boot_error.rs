@@ -0,0 +1,31 @@
+//! Visible error page for fatal failures.
+//!
+//! A panic aborts the wasm module and leaves a blank or frozen page, with
+//! the message only in the console. The panic hook installed here still
+//! logs through `console_error_panic_hook`, then fills in the `#boot-error`
+//! block from `index.html` (message plus a reload button). The page's own
+//! script uses the same block when the module fails to load at all.
+
+use std::panic;
+
+/// Install the panic hook. Call first thing in `main`.
+pub fn install() {
+    panic::set_hook(Box::new(|info| {
+        console_error_panic_hook::hook(info);
+        show(&info.to_string());
+    }));
+}
+
+/// Reveal `#boot-error` with `message`.
+fn show(message: &str) {
+    let Some(document) = web_sys::window().and_then(|w| w.document()) else {
+        return;
+    };
+    let Some(block) = document.get_element_by_id("boot-error") else {
+        return;
+    };
+    if let Ok(Some(pre)) = block.query_selector("pre") {
+        pre.set_text_content(Some(message));
+    }
+    let _ = block.remove_attribute("hidden");
+}
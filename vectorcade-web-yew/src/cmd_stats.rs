@@ -0,0 +1,66 @@
+//! Per-frame breakdown of the commands a game emits (F4).
+
+use glam::Vec2;
+use vectorcade_shared::draw::DrawCmd;
+use vectorcade_shared::game::ScreenInfo;
+use vectorcade_shared::Rgba;
+
+use crate::overlay::{self, LINE_ADVANCE};
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CmdHistogram {
+    clears: usize,
+    lines: usize,
+    polylines: usize,
+    /// Vertices across all polylines.
+    vertices: usize,
+    texts: usize,
+    /// Characters across all text commands.
+    chars: usize,
+    /// Everything else: layer, transform, and other state commands.
+    other: usize,
+}
+
+impl CmdHistogram {
+    pub fn of(cmds: &[DrawCmd]) -> Self {
+        let mut h = Self::default();
+        for cmd in cmds {
+            match cmd {
+                DrawCmd::Clear { .. } => h.clears += 1,
+                DrawCmd::Line(_) => h.lines += 1,
+                DrawCmd::Polyline { pts, .. } => {
+                    h.polylines += 1;
+                    h.vertices += pts.len();
+                }
+                DrawCmd::Text { text, .. } => {
+                    h.texts += 1;
+                    h.chars += text.chars().count();
+                }
+                _ => h.other += 1,
+            }
+        }
+        h
+    }
+
+    /// Draw the breakdown in the top-left corner.
+    pub fn draw(&self, screen: &ScreenInfo, out: &mut Vec<DrawCmd>) {
+        let rows = [
+            format!(
+                "CMDS {}",
+                self.clears + self.lines + self.polylines + self.texts + self.other
+            ),
+            format!("CLEAR {}", self.clears),
+            format!("LINE {}", self.lines),
+            format!("POLY {} / {} PTS", self.polylines, self.vertices),
+            format!("TEXT {} / {} CH", self.texts, self.chars),
+            format!("OTHER {}", self.other),
+        ];
+        let size = 10.0;
+        let step = size * LINE_ADVANCE * overlay::ndc_per_px(screen);
+        let color = Rgba(0.5, 0.9, 1.0, 0.8);
+        for (i, row) in rows.iter().enumerate() {
+            let pos = Vec2::new(-0.95, 0.9 - step * i as f32);
+            overlay::text(out, pos, row, size, color);
+        }
+    }
+}
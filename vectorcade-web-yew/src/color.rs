@@ -0,0 +1,110 @@
+//! Color math for platform-wide color effects.
+
+use vectorcade_shared::Rgba;
+
+/// Rotate the hue of `c` by `angle` radians, preserving alpha.
+///
+/// Uses a rotation about the gray (1, 1, 1) axis, which keeps grays gray and
+/// is cheap enough to run on every command each frame.
+pub fn rotate_hue(c: Rgba, angle: f32) -> Rgba {
+    let (sin, cos) = angle.sin_cos();
+    let k = (1.0 - cos) / 3.0;
+    let s = sin / 3.0_f32.sqrt();
+    let a = cos + k;
+    let b = k - s;
+    let d = k + s;
+    let r = a * c.0 + b * c.1 + d * c.2;
+    let g = d * c.0 + a * c.1 + b * c.2;
+    let bl = b * c.0 + d * c.1 + a * c.2;
+    Rgba(
+        r.clamp(0.0, 1.0),
+        g.clamp(0.0, 1.0),
+        bl.clamp(0.0, 1.0),
+        c.3,
+    )
+}
+
+/// Perceived brightness of `c` using Rec. 709 weights.
+pub fn luminance(c: Rgba) -> f32 {
+    0.2126 * c.0 + 0.7152 * c.1 + 0.0722 * c.2
+}
+
+/// Recolor `c` as a single-phosphor monitor would show it: the phosphor's
+/// hue at the original color's brightness.
+pub fn phosphor(c: Rgba, hue: [f32; 3]) -> Rgba {
+    let l = luminance(c);
+    Rgba(hue[0] * l, hue[1] * l, hue[2] * l, c.3)
+}
+
+/// Encode a linear-light color for display in sRGB, preserving alpha.
+///
+/// Channels are clamped to [0, 1] first so out-of-range values saturate
+/// instead of wrapping.
+pub fn linear_to_srgb(c: Rgba) -> Rgba {
+    let encode = |v: f32| {
+        let v = v.clamp(0.0, 1.0);
+        if v <= 0.003_130_8 {
+            v * 12.92
+        } else {
+            1.055 * v.powf(1.0 / 2.4) - 0.055
+        }
+    };
+    Rgba(encode(c.0), encode(c.1), encode(c.2), c.3.clamp(0.0, 1.0))
+}
+
+/// Format an RGB triple as a CSS `#rrggbb` string.
+pub fn to_hex(rgb: [f32; 3]) -> String {
+    let [r, g, b] = rgb.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// Parse a CSS `#rrggbb` string into an RGB triple.
+pub fn from_hex(hex: &str) -> Option<[f32; 3]> {
+    let hex = hex.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| {
+        u8::from_str_radix(hex.get(i..i + 2)?, 16)
+            .ok()
+            .map(|v| v as f32 / 255.0)
+    };
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(v: f32) -> f32 {
+        linear_to_srgb(Rgba(v, v, v, 1.0)).0
+    }
+
+    #[test]
+    fn srgb_endpoints_are_fixed() {
+        assert_eq!(encode(0.0), 0.0);
+        assert!((encode(1.0) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn srgb_knee_is_continuous() {
+        // Both segments give ~0.04045 at the knee.
+        let knee = 0.003_130_8;
+        assert!((encode(knee) - 0.040_45).abs() < 1e-4);
+        assert!((encode(knee + 1e-6) - encode(knee)).abs() < 1e-4);
+        assert!((encode(0.001) - 0.012_92).abs() < 1e-6);
+    }
+
+    #[test]
+    fn srgb_midpoint() {
+        assert!((encode(0.5) - 0.7354).abs() < 1e-3);
+    }
+
+    #[test]
+    fn srgb_clamps_and_keeps_alpha() {
+        let c = linear_to_srgb(Rgba(-1.0, 2.0, 0.5, 0.25));
+        assert_eq!(c.0, 0.0);
+        assert!((c.1 - 1.0).abs() < 1e-6);
+        assert_eq!(c.3, 0.25);
+    }
+}
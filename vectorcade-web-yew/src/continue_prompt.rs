@@ -0,0 +1,62 @@
+//! Arcade "CONTINUE?" countdown after game over.
+//!
+//! While the prompt is up the game is frozen with its state intact.
+//! Continuing unfreezes it; letting the countdown run out resets the game.
+
+use glam::Vec2;
+use vectorcade_shared::draw::DrawCmd;
+use vectorcade_shared::game::ScreenInfo;
+use vectorcade_shared::Rgba;
+use wasm_bindgen::prelude::*;
+
+use crate::{overlay, GAME_STATE};
+
+#[derive(Default)]
+pub struct ContinuePrompt {
+    /// When the countdown ends (ms), while the prompt is showing.
+    deadline: Option<f64>,
+}
+
+impl ContinuePrompt {
+    /// Start a countdown of `seconds` at `now` (ms).
+    pub fn offer(&mut self, now: f64, seconds: f32) {
+        self.deadline = Some(now + seconds as f64 * 1000.0);
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.deadline.is_some()
+    }
+
+    /// Dismiss the prompt (the player continued).
+    pub fn accept(&mut self) {
+        self.deadline = None;
+    }
+
+    /// Returns true once when the countdown runs out at `now`.
+    pub fn expired(&mut self, now: f64) -> bool {
+        if self.deadline.is_some_and(|d| now >= d) {
+            self.deadline = None;
+            return true;
+        }
+        false
+    }
+
+    pub fn draw(&self, now: f64, screen: &ScreenInfo, out: &mut Vec<DrawCmd>) {
+        let Some(deadline) = self.deadline else {
+            return;
+        };
+        let remaining = ((deadline - now) / 1000.0).ceil().max(0.0) as u32;
+        let color = Rgba(1.0, 0.9, 0.3, 1.0);
+        overlay::text_centered(out, screen, Vec2::new(0.0, 0.15), "CONTINUE?", 36.0, color);
+        let count = remaining.to_string();
+        overlay::text_centered(out, screen, Vec2::new(0.0, -0.1), &count, 64.0, color);
+    }
+}
+
+/// Report game over for the primary game, offering a continue.
+///
+/// Stands in for a game-reported game-over signal until games provide one.
+#[wasm_bindgen]
+pub fn game_over() {
+    GAME_STATE.with(|state| state.borrow_mut().offer_continue());
+}
@@ -0,0 +1,47 @@
+//! Containment for games that panic while resetting.
+//!
+//! A reset runs under `catch_unwind`, so where panics unwind the failing
+//! game is caught on the spot. wasm builds abort on panic instead, so the
+//! id of the game being reset is also written to `localStorage` first and
+//! cleared afterwards; a marker still present at the next load names the
+//! game that brought the page down.
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::{logging, storage};
+
+/// `localStorage` key holding the id of a game mid-reset.
+const RESETTING_KEY: &str = "vectorcade.resetting";
+
+/// Run `f` (a reset of game `id`), returning `None` if it panicked.
+pub fn guarded<R>(id: &str, f: impl FnOnce() -> R) -> Option<R> {
+    storage::save(RESETTING_KEY, &id);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    storage::remove(RESETTING_KEY);
+    match result {
+        Ok(value) => Some(value),
+        Err(payload) => {
+            logging::error(&format!("{} panicked in reset: {}", id, message(&*payload)));
+            None
+        }
+    }
+}
+
+/// Id of a game whose reset crashed the previous page load, if any.
+pub fn crashed_last_load() -> Option<String> {
+    let id = storage::load::<String>(RESETTING_KEY)?;
+    storage::remove(RESETTING_KEY);
+    logging::error(&format!("{} crashed the last load during reset", id));
+    Some(id)
+}
+
+fn message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "non-string panic payload"
+    }
+}
@@ -0,0 +1,59 @@
+//! Coin-op credits: insert coins, spend one to start a game.
+//!
+//! Disabled (free play) unless the credits setting is on.
+
+use glam::Vec2;
+use vectorcade_shared::draw::DrawCmd;
+use vectorcade_shared::game::ScreenInfo;
+use vectorcade_shared::Rgba;
+
+use crate::overlay::{self, TextDirection};
+
+/// Most credits a cabinet will hold, like a real coin counter.
+const MAX_CREDITS: u32 = 99;
+
+#[derive(Default)]
+pub struct Credits {
+    count: u32,
+    /// A credit has been spent on the current run.
+    started: bool,
+}
+
+impl Credits {
+    pub fn insert_coin(&mut self) {
+        self.count = (self.count + 1).min(MAX_CREDITS);
+    }
+
+    /// Spend a credit to start a run. Returns false if none are left.
+    ///
+    /// Once a run has started, further presses are free until `end_run`.
+    pub fn try_start(&mut self) -> bool {
+        if self.started {
+            return true;
+        }
+        self.started = self.spend();
+        self.started
+    }
+
+    /// Spend a credit mid-run (e.g. to continue). Returns false if none are left.
+    pub fn spend(&mut self) -> bool {
+        if self.count == 0 {
+            return false;
+        }
+        self.count -= 1;
+        true
+    }
+
+    /// Back to the menu: the next start costs a credit again.
+    pub fn end_run(&mut self) {
+        self.started = false;
+    }
+
+    /// Draw the credits counter in the bottom-right corner.
+    pub fn draw(&self, screen: &ScreenInfo, out: &mut Vec<DrawCmd>) {
+        let label = format!("CREDITS {}", self.count);
+        let color = Rgba(0.8, 0.8, 0.8, 0.8);
+        let corner = Vec2::new(0.95, -0.95);
+        overlay::text_directed(out, screen, corner, &label, 14.0, color, TextDirection::Rtl);
+    }
+}
@@ -0,0 +1,47 @@
+//! Crossfade between the outgoing and incoming game on a switch.
+//!
+//! The outgoing game's last frame is frozen and faded out over the incoming
+//! game, which fades in and updates normally from the first frame.
+
+use vectorcade_shared::draw::DrawCmd;
+
+use crate::display_list;
+use crate::view;
+
+/// Length of the fade (ms).
+const FADE_MS: f64 = 300.0;
+
+#[derive(Default)]
+pub struct Crossfade {
+    /// Frozen outgoing frame and the time the fade started (ms).
+    outgoing: Option<(Vec<DrawCmd>, f64)>,
+}
+
+impl Crossfade {
+    /// Fade out `frame` starting at `now`. With reduced motion the switch
+    /// stays an instant cut.
+    pub fn start(&mut self, mut frame: Vec<DrawCmd>, now: f64) {
+        if view::prefers_reduced_motion() {
+            return;
+        }
+        // The incoming game's clear is the one that counts.
+        frame.retain(|cmd| !matches!(cmd, DrawCmd::Clear { .. }));
+        self.outgoing = Some((frame, now));
+    }
+
+    /// Fade the incoming game's `cmds` in and draw the outgoing frame over them.
+    pub fn blend(&mut self, now: f64, cmds: &mut Vec<DrawCmd>) {
+        let Some((frame, started)) = &self.outgoing else {
+            return;
+        };
+        let t = ((now - started) / FADE_MS).clamp(0.0, 1.0) as f32;
+        if t >= 1.0 {
+            self.outgoing = None;
+            return;
+        }
+        display_list::for_each_color(cmds, |c| c.3 *= t);
+        let start = cmds.len();
+        cmds.extend(frame.iter().cloned());
+        display_list::for_each_color(&mut cmds[start..], |c| c.3 *= 1.0 - t);
+    }
+}
@@ -0,0 +1,77 @@
+//! Platform-drawn aiming crosshair for mouse-aimed games.
+//!
+//! Drawn into the game's own display list at the game-space pointer, so the
+//! orientation and scaling that follow put it exactly under the mouse. It is
+//! hidden while the pointer is off the canvas; under pointer lock it sits at
+//! the center of the playfield.
+
+use glam::Vec2;
+use vectorcade_shared::draw::DrawCmd;
+use vectorcade_shared::game::ScreenInfo;
+use vectorcade_shared::Rgba;
+
+use crate::overlay;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CrosshairShape {
+    Cross,
+    Circle,
+    Dot,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Crosshair {
+    pub shape: CrosshairShape,
+    /// Half the crosshair's extent, in pixels.
+    pub size_px: f32,
+    pub color: [f32; 3],
+}
+
+impl Crosshair {
+    /// Parse a `?crosshair=` value: `cross`, `circle`, or `dot`, in the
+    /// default size and color.
+    pub fn parse(s: &str) -> Option<Self> {
+        let shape = match s {
+            "cross" => CrosshairShape::Cross,
+            "circle" => CrosshairShape::Circle,
+            "dot" => CrosshairShape::Dot,
+            _ => return None,
+        };
+        Some(Self {
+            shape,
+            size_px: 10.0,
+            color: [0.4, 1.0, 0.4],
+        })
+    }
+
+    /// Draw centered on `at` (game NDC).
+    pub fn draw(&self, at: Vec2, screen: &ScreenInfo, out: &mut Vec<DrawCmd>) {
+        let [r, g, b] = self.color;
+        let color = Rgba(r, g, b, 0.9);
+        let r = self.size_px * overlay::ndc_per_px(screen);
+        match self.shape {
+            CrosshairShape::Cross => {
+                // Leave the center open so the target stays visible.
+                let gap = r * 0.3;
+                for dir in [Vec2::X, Vec2::NEG_X, Vec2::Y, Vec2::NEG_Y] {
+                    overlay::line(out, at + dir * gap, at + dir * r, color, 1.5);
+                }
+            }
+            CrosshairShape::Circle => {
+                const SEGMENTS: usize = 16;
+                let pts = (0..SEGMENTS)
+                    .map(|i| {
+                        let t = i as f32 / SEGMENTS as f32 * std::f32::consts::TAU;
+                        at + Vec2::from_angle(t) * r
+                    })
+                    .collect();
+                overlay::polyline(out, pts, true, color, 1.5);
+            }
+            CrosshairShape::Dot => {
+                // A short, thick stroke: zero-length lines may not draw.
+                let half = Vec2::X * r * 0.2;
+                overlay::line(out, at - half, at + half, color, self.size_px * 0.5);
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Collision-debug shapes (hitcircles, hitboxes) over the game, toggled
+//! with F10.
+//!
+//! Shapes go to their own buffer rather than the game's display list and
+//! are only collected while the toggle is on, so release frames never carry
+//! them. Coordinates are game-space NDC; the shapes are oriented and scaled
+//! with the game's output.
+
+use std::cell::RefCell;
+
+use glam::Vec2;
+use vectorcade_shared::draw::DrawCmd;
+use vectorcade_shared::Rgba;
+
+use crate::overlay;
+
+const COLOR: Rgba = Rgba(1.0, 0.2, 1.0, 0.8);
+const CIRCLE_SEGMENTS: usize = 24;
+
+thread_local! {
+    /// Shapes submitted this frame; `None` while debug drawing is off.
+    static SHAPES: RefCell<Option<Vec<DrawCmd>>> = const { RefCell::new(None) };
+}
+
+/// Turn debug drawing on or off; returns the new state.
+pub fn toggle() -> bool {
+    SHAPES.with(|shapes| {
+        let mut shapes = shapes.borrow_mut();
+        *shapes = match shapes.take() {
+            Some(_) => None,
+            None => Some(Vec::new()),
+        };
+        shapes.is_some()
+    })
+}
+
+fn submit(f: impl FnOnce(&mut Vec<DrawCmd>)) {
+    SHAPES.with(|shapes| {
+        if let Some(out) = shapes.borrow_mut().as_mut() {
+            f(out);
+        }
+    });
+}
+
+/// Outline a hitcircle.
+pub fn circle(center: Vec2, radius: f32) {
+    submit(|out| {
+        let pts = (0..CIRCLE_SEGMENTS)
+            .map(|i| {
+                let t = i as f32 / CIRCLE_SEGMENTS as f32 * std::f32::consts::TAU;
+                center + Vec2::from_angle(t) * radius
+            })
+            .collect();
+        overlay::polyline(out, pts, true, COLOR, 1.0);
+    });
+}
+
+/// Outline an axis-aligned hitbox.
+pub fn rect(min: Vec2, max: Vec2) {
+    submit(|out| overlay::rect(out, min, max, COLOR, 1.0));
+}
+
+/// Drop shapes submitted outside the frame (thumbnails, warm-up runs).
+pub fn clear() {
+    submit(Vec::clear);
+}
+
+/// Move this frame's shapes onto `out`.
+pub fn drain_into(out: &mut Vec<DrawCmd>) {
+    SHAPES.with(|shapes| {
+        if let Some(shapes) = shapes.borrow_mut().as_mut() {
+            out.append(shapes);
+        }
+    });
+}
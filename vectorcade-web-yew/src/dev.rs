@@ -0,0 +1,25 @@
+//! Developer hooks for iterating on games without a page reload.
+//!
+//! Wasm code can't be swapped in place, but game *state* can: these rebuild
+//! games from the registry so a dev setup that reloads the module (or tweaks
+//! registry-driven data) sees fresh instances immediately.
+
+use wasm_bindgen::prelude::*;
+
+use crate::GAME_STATE;
+
+/// Replace the running game with a fresh instance from `scripted::registry()`.
+///
+/// Unlike reset, this discards everything the game object holds, not just
+/// what its `reset` clears. Returns false if the game is no longer registered.
+#[wasm_bindgen]
+pub fn reload_game() -> bool {
+    GAME_STATE.with(|state| state.borrow_mut().reinstantiate_current())
+}
+
+/// Rebuild the whole game list from `scripted::registry()`, keeping the
+/// current game selected by id when it is still registered.
+#[wasm_bindgen]
+pub fn reload_registry() {
+    GAME_STATE.with(|state| state.borrow_mut().rebuild_registry());
+}
@@ -0,0 +1,73 @@
+//! Input devices a game needs, so players hear about it before they try.
+//!
+//! Each game's needs come from `GameProfile::needs`; a game with none plays
+//! with the keyboard alone. `?require=` and `?recommend=` (`pointer` or
+//! `gamepad`) add a need to every game, for trying the notices. The keyboard
+//! is assumed present; a mouse or pen is detected with `(any-pointer: fine)`
+//! and a gamepad by polling.
+
+use crate::{pad, query};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Device {
+    /// Mouse, trackpad or pen.
+    Pointer,
+    Gamepad,
+}
+
+impl Device {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "pointer" => Some(Self::Pointer),
+            "gamepad" => Some(Self::Gamepad),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Need {
+    /// Unplayable without the device.
+    Required,
+    /// Playable, but better with the device.
+    Recommended,
+}
+
+fn available(device: Device) -> bool {
+    match device {
+        Device::Pointer => web_sys::window()
+            .and_then(|w| w.match_media("(any-pointer: fine)").ok().flatten())
+            .is_some_and(|m| m.matches()),
+        Device::Gamepad => pad::connected().is_some(),
+    }
+}
+
+/// A notice when a device in `needs` is missing, most pressing need first.
+/// `touch_controls_off` adds a hint to try the on-screen controls in place
+/// of a missing gamepad.
+pub fn notice(needs: &[(Device, Need)], touch_controls_off: bool) -> Option<String> {
+    let from_url = [
+        ("require", Need::Required),
+        ("recommend", Need::Recommended),
+    ]
+    .into_iter()
+    .filter_map(|(param, need)| Some((Device::parse(&query::param(param)?)?, need)));
+    let (device, need) = needs
+        .iter()
+        .copied()
+        .chain(from_url)
+        .filter(|(device, _)| !available(*device))
+        .min_by_key(|(_, need)| *need != Need::Required)?;
+    let name = match device {
+        Device::Pointer => "MOUSE",
+        Device::Gamepad => "GAMEPAD",
+    };
+    let mut msg = match need {
+        Need::Required => format!("{} REQUIRED", name),
+        Need::Recommended => format!("BEST WITH A {}", name),
+    };
+    if device == Device::Gamepad && touch_controls_off {
+        msg.push_str(" - OR TRY TOUCH CONTROLS");
+    }
+    Some(msg)
+}
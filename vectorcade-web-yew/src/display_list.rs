@@ -0,0 +1,423 @@
+//! Passes that rewrite a frame's display list before it reaches the renderer.
+//!
+//! Platform-wide visual options are applied here so they work the same for
+//! every game without touching game code.
+
+use glam::Vec2;
+use vectorcade_shared::draw::{DrawCmd, Stroke};
+use vectorcade_shared::game::ScreenInfo;
+use vectorcade_shared::Rgba;
+
+use crate::overlay;
+use crate::profiles::ClearPolicy;
+
+/// Call `f` on the stroke of every line and polyline.
+pub fn for_each_stroke(cmds: &mut [DrawCmd], mut f: impl FnMut(&mut Stroke)) {
+    for cmd in cmds {
+        match cmd {
+            DrawCmd::Line(line) => f(&mut line.stroke),
+            DrawCmd::Polyline { stroke, .. } => f(stroke),
+            _ => {}
+        }
+    }
+}
+
+/// Call `f` on the color of every stroke and text command.
+///
+/// `Clear` colors are left alone so backgrounds stay put.
+pub fn for_each_color(cmds: &mut [DrawCmd], mut f: impl FnMut(&mut Rgba)) {
+    for cmd in cmds {
+        match cmd {
+            DrawCmd::Line(line) => f(&mut line.stroke.color),
+            DrawCmd::Polyline { stroke, .. } => f(&mut stroke.color),
+            DrawCmd::Text { color, .. } => f(color),
+            _ => {}
+        }
+    }
+}
+
+/// Call `f` on the color of every `Clear` command.
+pub fn for_each_clear(cmds: &mut [DrawCmd], mut f: impl FnMut(&mut Rgba)) {
+    for cmd in cmds {
+        if let DrawCmd::Clear { color } = cmd {
+            f(color);
+        }
+    }
+}
+
+/// Make a game's frame follow its clear policy.
+///
+/// An added clear is fully transparent, so it takes the platform background.
+pub fn apply_clear_policy(cmds: &mut Vec<DrawCmd>, policy: ClearPolicy) {
+    match policy {
+        ClearPolicy::AlwaysClear => {
+            if !matches!(cmds.first(), Some(DrawCmd::Clear { .. })) {
+                cmds.insert(
+                    0,
+                    DrawCmd::Clear {
+                        color: Rgba(0.0, 0.0, 0.0, 0.0),
+                    },
+                );
+            }
+        }
+        ClearPolicy::NeverClear => cmds.retain(|cmd| !matches!(cmd, DrawCmd::Clear { .. })),
+        ClearPolicy::GameControlled => {}
+    }
+}
+
+/// Give fully transparent `Clear` commands the platform background color.
+///
+/// A zero-alpha clear is the game's way of saying "use the host background".
+pub fn fill_transparent_clears(cmds: &mut [DrawCmd], background: Rgba) {
+    for cmd in cmds {
+        if let DrawCmd::Clear { color } = cmd {
+            if color.3 <= 0.0 {
+                *color = background;
+            }
+        }
+    }
+}
+
+/// Map every NDC point (line ends, polyline vertices, text anchors) through `f`.
+pub fn map_points(cmds: &mut [DrawCmd], f: impl Fn(Vec2) -> Vec2) {
+    for cmd in cmds {
+        match cmd {
+            DrawCmd::Line(line) => {
+                line.a = f(line.a);
+                line.b = f(line.b);
+            }
+            DrawCmd::Polyline { pts, .. } => {
+                for p in pts.iter_mut() {
+                    *p = f(*p);
+                }
+            }
+            DrawCmd::Text { pos, .. } => *pos = f(*pos),
+            _ => {}
+        }
+    }
+}
+
+/// Snap every point to a grid of `grid_px` device pixels for a stepped,
+/// chunky look. Unlike integer scaling this moves vertices, not the image.
+pub fn quantize(cmds: &mut [DrawCmd], screen: &ScreenInfo, grid_px: f32) {
+    if grid_px <= 0.0 {
+        return;
+    }
+    // Pixels are square in NDC: the view fits the short axis.
+    let step = grid_px * screen.dpi_scale * overlay::ndc_per_px(screen);
+    map_points(cmds, |p| (p / step).round() * step);
+}
+
+/// Multiply every text command's `size_px` by `factor`.
+pub fn scale_text(cmds: &mut [DrawCmd], factor: f32) {
+    for cmd in cmds {
+        if let DrawCmd::Text { size_px, .. } = cmd {
+            *size_px *= factor;
+        }
+    }
+}
+
+/// Give the strokes in `cmds[start..]` a glow in `glow_color` instead of
+/// their own color.
+///
+/// `Stroke` has no separate glow color, so each stroke becomes two: a halo
+/// copy in `glow_color` that keeps the glow, then the original core with its
+/// glow removed drawn on top.
+pub fn glow_in_color(cmds: &mut Vec<DrawCmd>, start: usize, glow_color: Rgba) {
+    let tail: Vec<DrawCmd> = cmds.drain(start.min(cmds.len())..).collect();
+    for mut core in tail {
+        let mut halo = core.clone();
+        let mut is_stroke = false;
+        for_each_stroke(std::slice::from_mut(&mut halo), |s| {
+            s.color = Rgba(glow_color.0, glow_color.1, glow_color.2, s.color.3);
+            is_stroke = true;
+        });
+        if is_stroke {
+            cmds.push(halo);
+            for_each_stroke(std::slice::from_mut(&mut core), |s| s.glow = 0.0);
+        }
+        cmds.push(core);
+    }
+}
+
+/// Draw every glow in one layer under all the crisp cores.
+///
+/// Halo copies of the glowing strokes go first, at reduced alpha, then the
+/// frame itself with its glow removed. Piled-up halos in dense scenes then
+/// brighten the background instead of washing the lines out to white.
+/// Commands before the last `Clear` are left alone.
+pub fn layer_glow(cmds: &mut Vec<DrawCmd>) {
+    const HALO_ALPHA: f32 = 0.6;
+    let start = cmds
+        .iter()
+        .rposition(|cmd| matches!(cmd, DrawCmd::Clear { .. }))
+        .map_or(0, |i| i + 1);
+    let mut halos: Vec<DrawCmd> = cmds[start..]
+        .iter()
+        .filter(|cmd| match cmd {
+            DrawCmd::Line(line) => line.stroke.glow > 0.0,
+            DrawCmd::Polyline { stroke, .. } => stroke.glow > 0.0,
+            _ => false,
+        })
+        .cloned()
+        .collect();
+    for_each_stroke(&mut halos, |s| s.color.3 *= HALO_ALPHA);
+    for_each_stroke(&mut cmds[start..], |s| s.glow = 0.0);
+    cmds.splice(start..start, halos);
+}
+
+/// Draw every stroke as a beam: the original as a dimmer outer pass, then a
+/// core `core_ratio` times as wide on top, blended toward white.
+///
+/// Approximates a vector beam's bright center and soft falloff without the
+/// cost of a bloom pass.
+pub fn beam_core(cmds: &mut Vec<DrawCmd>, core_ratio: f32) {
+    const OUTER_DIM: f32 = 0.6;
+    const CORE_WHITEN: f32 = 0.6;
+    let frame = std::mem::take(cmds);
+    for mut outer in frame {
+        let mut core = outer.clone();
+        let mut is_stroke = false;
+        for_each_stroke(std::slice::from_mut(&mut outer), |s| {
+            s.color = Rgba(
+                s.color.0 * OUTER_DIM,
+                s.color.1 * OUTER_DIM,
+                s.color.2 * OUTER_DIM,
+                s.color.3,
+            );
+            is_stroke = true;
+        });
+        cmds.push(outer);
+        if is_stroke {
+            for_each_stroke(std::slice::from_mut(&mut core), |s| {
+                let c = s.color;
+                let toward_white = |v: f32| v + (1.0 - v) * CORE_WHITEN;
+                s.color = Rgba(toward_white(c.0), toward_white(c.1), toward_white(c.2), c.3);
+                s.width_px *= core_ratio;
+                s.glow = 0.0;
+            });
+            cmds.push(core);
+        }
+    }
+}
+
+/// Show only the first `t` (0..=1) of every stroke's length and text's
+/// characters, as if the beam were still drawing them.
+///
+/// Each path is cut independently, partway through the segment where its
+/// length runs out; paths not yet started are dropped.
+pub fn reveal(cmds: &mut Vec<DrawCmd>, t: f32) {
+    if t >= 1.0 {
+        return;
+    }
+    let t = t.max(0.0);
+    // Points along `pts` up to fraction `t` of its length.
+    let cut = |pts: &[Vec2]| -> Vec<Vec2> {
+        let total: f32 = pts.windows(2).map(|w| w[0].distance(w[1])).sum();
+        let mut left = total * t;
+        let mut out = Vec::with_capacity(pts.len());
+        out.extend(pts.first().copied());
+        for w in pts.windows(2) {
+            let len = w[0].distance(w[1]);
+            if left >= len {
+                out.push(w[1]);
+                left -= len;
+            } else {
+                out.push(w[0].lerp(w[1], left / len.max(f32::EPSILON)));
+                break;
+            }
+        }
+        out
+    };
+    cmds.retain_mut(|cmd| match cmd {
+        DrawCmd::Line(line) => {
+            line.b = line.a.lerp(line.b, t);
+            t > 0.0
+        }
+        DrawCmd::Polyline { pts, closed, .. } => {
+            if *closed {
+                pts.extend(pts.first().copied());
+                *closed = false;
+            }
+            *pts = cut(pts);
+            pts.len() >= 2 && t > 0.0
+        }
+        DrawCmd::Text { text, .. } => {
+            let shown = (text.chars().count() as f32 * t).floor() as usize;
+            *text = text.chars().take(shown).collect();
+            !text.is_empty()
+        }
+        _ => true,
+    });
+}
+
+/// Cut the list off once it holds more than `budget` primitives (lines,
+/// polyline segments and text glyphs). Returns true if anything was cut.
+///
+/// A safety valve against a runaway game: everything from the command that
+/// crosses the budget on is dropped.
+pub fn limit_primitives(cmds: &mut Vec<DrawCmd>, budget: usize) -> bool {
+    let mut used = 0;
+    let over = cmds.iter().position(|cmd| {
+        used += primitives(cmd);
+        used > budget
+    });
+    match over {
+        Some(idx) => {
+            cmds.truncate(idx);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Simulate an overloaded vector beam: only `budget` primitives per frame
+/// are drawn at full brightness and the rest are dimmed.
+///
+/// The lit window advances by `budget` primitives each `frame`, wrapping,
+/// so a heavy scene flickers all over as on real hardware instead of its
+/// tail going dark. Whole commands are lit or dimmed, by where they start.
+pub fn beam_budget(cmds: &mut [DrawCmd], budget: usize, frame: u64) {
+    const DIM: f32 = 0.25;
+    let total: usize = cmds.iter().map(primitives).sum();
+    if budget == 0 || total <= budget {
+        return;
+    }
+    // In u64: the product overflows a wasm32 `usize` on heavy scenes.
+    let (total_64, budget_64) = (total as u64, budget as u64);
+    let start = ((frame % total_64) * budget_64 % total_64) as usize;
+    let end = start + budget;
+    let mut at = 0;
+    for cmd in cmds.iter_mut() {
+        let n = primitives(cmd);
+        let lit = (start..end).contains(&at) || (start..end).contains(&(at + total));
+        if n > 0 && !lit {
+            for_each_color(std::slice::from_mut(cmd), |c| c.3 *= DIM);
+        }
+        at += n;
+    }
+}
+
+/// Lines, polyline segments and text glyphs in one command.
+fn primitives(cmd: &DrawCmd) -> usize {
+    match cmd {
+        DrawCmd::Line(_) => 1,
+        DrawCmd::Polyline { pts, closed, .. } => pts.len().saturating_sub(1) + usize::from(*closed),
+        DrawCmd::Text { text, .. } => text.chars().count(),
+        _ => 0,
+    }
+}
+
+/// How far glow spreads past a stroke per unit of `Stroke::glow` (px).
+pub const GLOW_REACH_PX: f32 = 12.0;
+
+/// Drop lines and polylines lying wholly outside the visible NDC box.
+///
+/// The renderer fits the NDC square to the canvas's shorter side, so the
+/// visible box reaches past ±1 along the longer one. Each primitive's
+/// bounding box is grown by its half width plus glow reach, so strokes just
+/// off-screen whose glow bleeds in are kept. Partially visible primitives
+/// are left whole. Returns the number dropped.
+pub fn cull_offscreen(cmds: &mut Vec<DrawCmd>, screen: &ScreenInfo) -> usize {
+    let short = screen.width_px.min(screen.height_px).max(1) as f32;
+    let half_extent = Vec2::new(
+        screen.width_px.max(1) as f32,
+        screen.height_px.max(1) as f32,
+    ) / short;
+    let px = overlay::ndc_per_px(screen);
+    let visible = |pts: &[Vec2], stroke: &Stroke| {
+        let reach = stroke.width_px * 0.5 + stroke.glow.max(0.0) * GLOW_REACH_PX;
+        let bound = half_extent + Vec2::splat(px * reach * screen.dpi_scale);
+        let (min, max) = pts.iter().fold(
+            (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+            |(min, max), p| (min.min(*p), max.max(*p)),
+        );
+        min.cmple(bound).all() && max.cmpge(-bound).all()
+    };
+    let before = cmds.len();
+    cmds.retain(|cmd| match cmd {
+        DrawCmd::Line(line) => visible(&[line.a, line.b], &line.stroke),
+        DrawCmd::Polyline { pts, stroke, .. } => visible(pts, stroke),
+        _ => true,
+    });
+    before - cmds.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vectorcade_shared::draw::Line2;
+    use vectorcade_shared::font::FontStyleId;
+
+    fn screen(width_px: u32, height_px: u32) -> ScreenInfo {
+        ScreenInfo {
+            width_px,
+            height_px,
+            dpi_scale: 1.0,
+        }
+    }
+
+    fn line(a: Vec2, b: Vec2) -> DrawCmd {
+        DrawCmd::Line(Line2 {
+            a,
+            b,
+            stroke: Stroke {
+                color: Rgba(1.0, 1.0, 1.0, 1.0),
+                width_px: 2.0,
+                glow: 0.0,
+            },
+        })
+    }
+
+    #[test]
+    fn cull_keeps_visible_sides_of_wide_canvas() {
+        // 1600x800: x is visible out to ±2.
+        let mut cmds = vec![
+            line(Vec2::new(1.5, 0.0), Vec2::new(1.9, 0.0)),
+            line(Vec2::new(-1.9, 0.5), Vec2::new(-1.5, 0.5)),
+            line(Vec2::new(2.5, 0.0), Vec2::new(3.0, 0.0)),
+            line(Vec2::new(0.0, 1.5), Vec2::new(0.5, 1.5)),
+        ];
+        assert_eq!(cull_offscreen(&mut cmds, &screen(1600, 800)), 2);
+        assert_eq!(cmds.len(), 2);
+    }
+
+    #[test]
+    fn cull_keeps_visible_ends_of_tall_canvas() {
+        // 800x1600: y is visible out to ±2.
+        let mut cmds = vec![
+            line(Vec2::new(0.0, 1.5), Vec2::new(0.0, 1.9)),
+            line(Vec2::new(0.5, -1.9), Vec2::new(0.5, -1.5)),
+            line(Vec2::new(0.0, 2.5), Vec2::new(0.0, 3.0)),
+            line(Vec2::new(1.5, 0.0), Vec2::new(1.5, 0.5)),
+        ];
+        assert_eq!(cull_offscreen(&mut cmds, &screen(800, 1600)), 2);
+        assert_eq!(cmds.len(), 2);
+    }
+
+    #[test]
+    fn cull_margin_follows_short_side() {
+        // A 2 px line is 1 px wide each side: 0.0025 NDC on an 800 px short side.
+        let mut cmds = vec![
+            line(Vec2::new(2.002, 0.0), Vec2::new(2.5, 0.0)),
+            line(Vec2::new(2.004, 0.0), Vec2::new(2.5, 0.0)),
+        ];
+        assert_eq!(cull_offscreen(&mut cmds, &screen(1600, 800)), 1);
+    }
+
+    #[test]
+    fn text_counts_glyphs_not_bytes() {
+        let text = |text: &str| DrawCmd::Text {
+            pos: Vec2::ZERO,
+            text: text.to_string(),
+            size_px: 16.0,
+            color: Rgba(1.0, 1.0, 1.0, 1.0),
+            style: FontStyleId::ATARI,
+        };
+        assert_eq!(primitives(&text("ÉCRAN")), 5);
+        let mut cmds = vec![text("ÉÉ"), text("É")];
+        assert!(!limit_primitives(&mut cmds, 3));
+        assert!(limit_primitives(&mut cmds, 2));
+        assert_eq!(cmds.len(), 1);
+    }
+}
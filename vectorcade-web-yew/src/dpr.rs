@@ -0,0 +1,55 @@
+//! Device pixel ratio change detection.
+//!
+//! Moving the window to a monitor with a different scale changes
+//! `devicePixelRatio`. The frame loop re-reads the ratio every frame, so the
+//! backing size follows by itself; this catches the change as it happens
+//! (a `matchMedia` query on the current resolution, re-armed at the new one)
+//! so instances can drop state they recorded in device pixels.
+
+use std::cell::{Cell, RefCell};
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::MediaQueryList;
+
+use crate::logging;
+
+thread_local! {
+    /// Bumped on every ratio change.
+    static GENERATION: Cell<u32> = const { Cell::new(0) };
+    /// The armed query and its listener, replaced on every change.
+    static QUERY: RefCell<Option<(MediaQueryList, Closure<dyn Fn()>)>> = const { RefCell::new(None) };
+}
+
+/// Listen for the ratio to leave its current value.
+pub fn watch() {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let dpr = window.device_pixel_ratio();
+    let Ok(Some(query)) = window.match_media(&format!("(resolution: {}dppx)", dpr)) else {
+        return;
+    };
+    let on_change = Closure::<dyn Fn()>::new(|| {
+        GENERATION.with(|g| g.set(g.get() + 1));
+        // Re-arm at the new ratio; this also drops the query that fired.
+        watch();
+    });
+    if query
+        .add_event_listener_with_callback("change", on_change.as_ref().unchecked_ref())
+        .is_err()
+    {
+        return;
+    }
+    logging::debug(&format!("watching for pixel ratio changes from {}", dpr));
+    let previous = QUERY.with(|q| q.replace(Some((query, on_change))));
+    if let Some((query, listener)) = previous {
+        let _ =
+            query.remove_event_listener_with_callback("change", listener.as_ref().unchecked_ref());
+    }
+}
+
+/// Number of ratio changes seen so far.
+pub fn generation() -> u32 {
+    GENERATION.with(Cell::get)
+}
@@ -0,0 +1,86 @@
+//! Fixed-step drift diagnostic, enabled with `?drift=1`.
+//!
+//! Counts the fixed steps each frame takes and warns when the count is off
+//! for the display rate (exactly 1 at 60 Hz, 0 or 1 above it). Game time
+//! (steps × `TIMESTEP`) is also compared with wall time: a game that moves
+//! by `now_s` instead of its `dt` shows up as behaving differently at high
+//! refresh rates while the drift stays flat. Read-only; the loop is untouched.
+
+use glam::Vec2;
+use vectorcade_shared::draw::DrawCmd;
+use vectorcade_shared::game::ScreenInfo;
+use vectorcade_shared::Rgba;
+
+use crate::{logging, overlay, query, TIMESTEP};
+
+/// Displays up to this rate are treated as 60 Hz.
+const SIXTY_HZ_MAX: f64 = 75.0;
+/// Minimum gap between console warnings.
+const WARN_INTERVAL_MS: f64 = 1000.0;
+
+#[derive(Default)]
+pub struct Drift {
+    steps: u64,
+    wall_s: f64,
+    last_steps: u32,
+    off_frames: u32,
+    last_warn: f64,
+}
+
+impl Drift {
+    /// A diagnostic if the page was opened with `?drift=1`.
+    pub fn from_url() -> Option<Self> {
+        query::param("drift")
+            .is_some_and(|v| v == "1")
+            .then(Self::default)
+    }
+
+    /// Record a frame at `now` (ms) that was `dt_s` of wall time (before the
+    /// accumulator clamp) and ran `steps` fixed steps.
+    pub fn frame(&mut self, now: f64, dt_s: f64, steps: u32, refresh_hz: Option<f64>) {
+        self.steps += u64::from(steps);
+        self.wall_s += dt_s;
+        self.last_steps = steps;
+        let Some(hz) = refresh_hz else {
+            return;
+        };
+        let expected = if hz <= SIXTY_HZ_MAX { 1..=1 } else { 0..=1 };
+        if expected.contains(&steps) {
+            return;
+        }
+        self.off_frames += 1;
+        if now - self.last_warn >= WARN_INTERVAL_MS {
+            self.last_warn = now;
+            logging::warn(&format!(
+                "timestep: {} steps in a {:.1}ms frame at {:.0} Hz (expected {}..={}); drift {:+.3}s",
+                steps,
+                dt_s * 1000.0,
+                hz,
+                expected.start(),
+                expected.end(),
+                self.drift_s()
+            ));
+        }
+    }
+
+    /// Game time minus wall time, in seconds.
+    fn drift_s(&self) -> f64 {
+        self.steps as f64 * TIMESTEP as f64 - self.wall_s
+    }
+
+    /// Draw the counters in the bottom-left corner.
+    pub fn draw(&self, screen: &ScreenInfo, out: &mut Vec<DrawCmd>) {
+        let rows = [
+            format!("DRIFT {:+.3}S", self.drift_s()),
+            format!("STEPS {} LAST {}", self.steps, self.last_steps),
+            format!("OFF FRAMES {}", self.off_frames),
+        ];
+        let size = 10.0;
+        let step = size * overlay::LINE_ADVANCE * overlay::ndc_per_px(screen);
+        let color = Rgba(1.0, 0.8, 0.4, 0.8);
+        for (i, row) in rows.iter().rev().enumerate() {
+            let pos = Vec2::new(-0.95, -0.9 + step * i as f32);
+            overlay::text(out, pos, row, size, color);
+        }
+    }
+}
@@ -0,0 +1,414 @@
+//! Frame export for external renderers (Three.js, SVG, ...).
+//!
+//! When enabled with `set_frame_export(true)`, each frame's final display list
+//! is serialized to JSON and can be read with `frame_json()`. The format is
+//! this module's own, so it stays stable if `DrawCmd` grows:
+//!
+//! ```json
+//! [{"type":"clear","color":[0,0,0,1]},
+//!  {"type":"line","a":[x,y],"b":[x,y],"stroke":{"color":[r,g,b,a],"width_px":2,"glow":1}},
+//!  {"type":"polyline","pts":[[x,y],...],"closed":true,"stroke":{...}},
+//!  {"type":"text","pos":[x,y],"text":"HI","size_px":24,"color":[r,g,b,a]}]
+//! ```
+//!
+//! Coordinates are NDC. Serializing every frame is costly, so export is off
+//! by default.
+//!
+//! For per-frame streaming, `set_frame_export_binary(true)` keeps a compact
+//! encoding instead, read with `frame_binary()` as a `Uint8Array` and parsed
+//! with a `DataView`. All numbers are little-endian `f32` unless noted; each
+//! command is an opcode byte followed by its fields:
+//!
+//! | Op | Command | Fields |
+//! |----|---------|--------|
+//! | 0 | clear | color (4) |
+//! | 1 | line | a (2), b (2), stroke |
+//! | 2 | polyline | closed (`u8`), point count (`u32`), stroke, points (2 each) |
+//! | 3 | text | pos (2), size_px (1), color (4), byte length (`u32`), UTF-8 |
+//!
+//! A stroke is color (4), width_px (1), glow (1). Fields are packed with no
+//! padding; commands without an export format are left out, as in JSON.
+//! `decode_frame_binary(bytes)` turns a buffer back into the JSON format.
+
+use std::fmt;
+
+use glam::Vec2;
+use serde::Serialize;
+use vectorcade_shared::draw::{DrawCmd, Line2, Stroke};
+use vectorcade_shared::font::FontStyleId;
+use vectorcade_shared::Rgba;
+use wasm_bindgen::prelude::*;
+
+use crate::GAME_STATE;
+
+#[derive(Serialize)]
+struct ExportStroke {
+    color: [f32; 4],
+    width_px: f32,
+    glow: f32,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ExportCmd<'a> {
+    Clear {
+        color: [f32; 4],
+    },
+    Line {
+        a: [f32; 2],
+        b: [f32; 2],
+        stroke: ExportStroke,
+    },
+    Polyline {
+        pts: Vec<[f32; 2]>,
+        closed: bool,
+        stroke: ExportStroke,
+    },
+    Text {
+        pos: [f32; 2],
+        text: &'a str,
+        size_px: f32,
+        color: [f32; 4],
+    },
+}
+
+fn rgba(c: Rgba) -> [f32; 4] {
+    [c.0, c.1, c.2, c.3]
+}
+
+fn stroke(s: &Stroke) -> ExportStroke {
+    ExportStroke {
+        color: rgba(s.color),
+        width_px: s.width_px,
+        glow: s.glow,
+    }
+}
+
+fn export_cmd(cmd: &DrawCmd) -> Option<ExportCmd<'_>> {
+    Some(match cmd {
+        DrawCmd::Clear { color } => ExportCmd::Clear {
+            color: rgba(*color),
+        },
+        DrawCmd::Line(line) => ExportCmd::Line {
+            a: line.a.to_array(),
+            b: line.b.to_array(),
+            stroke: stroke(&line.stroke),
+        },
+        DrawCmd::Polyline {
+            pts,
+            closed,
+            stroke: s,
+        } => ExportCmd::Polyline {
+            pts: pts.iter().map(|p| p.to_array()).collect(),
+            closed: *closed,
+            stroke: stroke(s),
+        },
+        DrawCmd::Text {
+            pos,
+            text,
+            size_px,
+            color,
+            ..
+        } => ExportCmd::Text {
+            pos: pos.to_array(),
+            text,
+            size_px: *size_px,
+            color: rgba(*color),
+        },
+        // Variants without an export format yet are left out.
+        _ => return None,
+    })
+}
+
+/// Serialize a display list to the export JSON format.
+pub fn to_json(cmds: &[DrawCmd]) -> String {
+    let out: Vec<ExportCmd> = cmds.iter().filter_map(export_cmd).collect();
+    serde_json::to_string(&out).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Encode a display list in the binary export format.
+pub fn encode_draw_cmds(cmds: &[DrawCmd]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let f32s = |out: &mut Vec<u8>, vals: &[f32]| {
+        for v in vals {
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+    };
+    let stroke = |out: &mut Vec<u8>, s: &Stroke| {
+        f32s(out, &rgba(s.color));
+        f32s(out, &[s.width_px, s.glow]);
+    };
+    for cmd in cmds {
+        match cmd {
+            DrawCmd::Clear { color } => {
+                out.push(0);
+                f32s(&mut out, &rgba(*color));
+            }
+            DrawCmd::Line(line) => {
+                out.push(1);
+                f32s(&mut out, &[line.a.x, line.a.y, line.b.x, line.b.y]);
+                stroke(&mut out, &line.stroke);
+            }
+            DrawCmd::Polyline {
+                pts,
+                closed,
+                stroke: s,
+            } => {
+                out.push(2);
+                out.push(u8::from(*closed));
+                out.extend_from_slice(&(pts.len() as u32).to_le_bytes());
+                stroke(&mut out, s);
+                for p in pts {
+                    f32s(&mut out, &[p.x, p.y]);
+                }
+            }
+            DrawCmd::Text {
+                pos,
+                text,
+                size_px,
+                color,
+                ..
+            } => {
+                out.push(3);
+                f32s(&mut out, &[pos.x, pos.y, *size_px]);
+                f32s(&mut out, &rgba(*color));
+                out.extend_from_slice(&(text.len() as u32).to_le_bytes());
+                out.extend_from_slice(text.as_bytes());
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Why a binary frame could not be decoded.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The buffer ends inside the command starting at this offset.
+    Truncated(usize),
+    /// Unknown opcode at this offset.
+    UnknownOp(u8, usize),
+    /// Text bytes that are not UTF-8, in the command at this offset.
+    InvalidText(usize),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::Truncated(at) => write!(f, "truncated command at byte {}", at),
+            DecodeError::UnknownOp(op, at) => write!(f, "unknown opcode {} at byte {}", op, at),
+            DecodeError::InvalidText(at) => write!(f, "text at byte {} is not UTF-8", at),
+        }
+    }
+}
+
+/// Reads fields of the command starting at `start`.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    start: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], DecodeError> {
+        let bytes = self.bytes[self.pos..]
+            .get(..n)
+            .ok_or(DecodeError::Truncated(self.start))?;
+        self.pos += n;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, DecodeError> {
+        let b = self.take(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn f32(&mut self) -> Result<f32, DecodeError> {
+        Ok(f32::from_bits(self.u32()?))
+    }
+
+    fn vec2(&mut self) -> Result<Vec2, DecodeError> {
+        Ok(Vec2::new(self.f32()?, self.f32()?))
+    }
+
+    fn rgba(&mut self) -> Result<Rgba, DecodeError> {
+        Ok(Rgba(self.f32()?, self.f32()?, self.f32()?, self.f32()?))
+    }
+
+    fn stroke(&mut self) -> Result<Stroke, DecodeError> {
+        Ok(Stroke {
+            color: self.rgba()?,
+            width_px: self.f32()?,
+            glow: self.f32()?,
+        })
+    }
+}
+
+/// Decode a buffer in the binary export format.
+///
+/// Text comes back in the default font style, which the format leaves out.
+pub fn decode_draw_cmds(bytes: &[u8]) -> Result<Vec<DrawCmd>, DecodeError> {
+    let mut r = Reader {
+        bytes,
+        pos: 0,
+        start: 0,
+    };
+    let mut cmds = Vec::new();
+    while r.pos < bytes.len() {
+        r.start = r.pos;
+        let cmd = match r.u8()? {
+            0 => DrawCmd::Clear { color: r.rgba()? },
+            1 => DrawCmd::Line(Line2 {
+                a: r.vec2()?,
+                b: r.vec2()?,
+                stroke: r.stroke()?,
+            }),
+            2 => {
+                let closed = r.u8()? != 0;
+                let count = r.u32()? as usize;
+                let stroke = r.stroke()?;
+                // Check the length before allocating for a bogus count.
+                if bytes.len() - r.pos < count.saturating_mul(8) {
+                    return Err(DecodeError::Truncated(r.start));
+                }
+                let pts = (0..count).map(|_| r.vec2()).collect::<Result<_, _>>()?;
+                DrawCmd::Polyline {
+                    pts,
+                    closed,
+                    stroke,
+                }
+            }
+            3 => {
+                let pos = r.vec2()?;
+                let size_px = r.f32()?;
+                let color = r.rgba()?;
+                let len = r.u32()? as usize;
+                let text = std::str::from_utf8(r.take(len)?)
+                    .map_err(|_| DecodeError::InvalidText(r.start))?
+                    .to_string();
+                DrawCmd::Text {
+                    pos,
+                    text,
+                    size_px,
+                    color,
+                    style: FontStyleId::ATARI,
+                }
+            }
+            op => return Err(DecodeError::UnknownOp(op, r.start)),
+        };
+        cmds.push(cmd);
+    }
+    Ok(cmds)
+}
+
+/// Turn per-frame JSON export on or off.
+#[wasm_bindgen]
+pub fn set_frame_export(enabled: bool) {
+    GAME_STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        state.frame_json = enabled.then(String::new);
+    });
+}
+
+/// The last frame's display list as JSON, or `undefined` when export is off.
+#[wasm_bindgen]
+pub fn frame_json() -> Option<String> {
+    GAME_STATE.with(|state| state.borrow().frame_json.clone())
+}
+
+/// Turn per-frame binary export on or off.
+#[wasm_bindgen]
+pub fn set_frame_export_binary(enabled: bool) {
+    GAME_STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        state.frame_binary = enabled.then(Vec::new);
+    });
+}
+
+/// The last frame's display list in the binary format, or `undefined` when
+/// binary export is off.
+#[wasm_bindgen]
+pub fn frame_binary() -> Option<Vec<u8>> {
+    GAME_STATE.with(|state| state.borrow().frame_binary.clone())
+}
+
+/// Convert a binary frame back to the export JSON, for tools that only
+/// read JSON.
+#[wasm_bindgen]
+pub fn decode_frame_binary(bytes: &[u8]) -> Result<String, JsValue> {
+    decode_draw_cmds(bytes)
+        .map(|cmds| to_json(&cmds))
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stroke() -> Stroke {
+        Stroke {
+            color: Rgba(0.1, 0.2, 0.3, 0.4),
+            width_px: 2.5,
+            glow: 0.75,
+        }
+    }
+
+    fn sample() -> Vec<DrawCmd> {
+        vec![
+            DrawCmd::Clear {
+                color: Rgba(0.0, 0.0, 0.1, 1.0),
+            },
+            DrawCmd::Line(Line2 {
+                a: Vec2::new(-0.5, 0.25),
+                b: Vec2::new(0.5, -0.25),
+                stroke: stroke(),
+            }),
+            DrawCmd::Polyline {
+                pts: vec![Vec2::ZERO, Vec2::X, Vec2::Y],
+                closed: true,
+                stroke: stroke(),
+            },
+            DrawCmd::Text {
+                pos: Vec2::new(-0.9, 0.9),
+                text: "SCORE Ü".to_string(),
+                size_px: 24.0,
+                color: Rgba(1.0, 1.0, 0.0, 1.0),
+                style: FontStyleId::ATARI,
+            },
+        ]
+    }
+
+    #[test]
+    fn binary_round_trips() {
+        let cmds = sample();
+        let decoded = decode_draw_cmds(&encode_draw_cmds(&cmds)).unwrap();
+        // `DrawCmd` equality is compared through the JSON export.
+        assert_eq!(decoded.len(), cmds.len());
+        assert_eq!(to_json(&decoded), to_json(&cmds));
+    }
+
+    #[test]
+    fn truncated_input_is_an_error() {
+        let bytes = encode_draw_cmds(&sample());
+        // The text command starts after clear (17), line (41) and the
+        // polyline (30 + 3 points).
+        let text_at = 17 + 41 + 30 + 24;
+        assert_eq!(
+            decode_draw_cmds(&bytes[..bytes.len() - 1]),
+            Err(DecodeError::Truncated(text_at))
+        );
+        assert_eq!(
+            decode_draw_cmds(&bytes[..3]),
+            Err(DecodeError::Truncated(0))
+        );
+    }
+
+    #[test]
+    fn unknown_opcode_is_an_error() {
+        assert_eq!(decode_draw_cmds(&[9]), Err(DecodeError::UnknownOp(9, 0)));
+    }
+}
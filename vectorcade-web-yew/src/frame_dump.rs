@@ -0,0 +1,103 @@
+//! Burst capture of consecutive frames to PNG files (F9).
+//!
+//! Each frame is read back with `to_data_url` right after it renders, and
+//! the set is offered as numbered downloads once the burst completes. The
+//! counter is a DOM element rather than vector text so it stays out of the
+//! captured frames.
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlAnchorElement, HtmlCanvasElement};
+
+use crate::logging;
+
+/// Upper bound on frames per burst; each PNG data URL is held in memory
+/// until the burst ends.
+pub const MAX_FRAMES: u32 = 120;
+
+#[derive(Default)]
+pub struct FrameDump {
+    target: u32,
+    frames: Vec<String>,
+    counter: Option<Element>,
+}
+
+impl FrameDump {
+    /// Capture the next `count` frames (capped at `MAX_FRAMES`).
+    pub fn arm(&mut self, count: u32) {
+        if self.is_capturing() {
+            return;
+        }
+        self.target = count.clamp(1, MAX_FRAMES);
+        self.frames = Vec::with_capacity(self.target as usize);
+        self.counter = show_counter();
+        self.update_counter();
+    }
+
+    pub fn is_capturing(&self) -> bool {
+        self.target > 0
+    }
+
+    /// Read back the frame just rendered to `canvas`.
+    pub fn capture(&mut self, canvas: &HtmlCanvasElement) {
+        if !self.is_capturing() {
+            return;
+        }
+        match canvas.to_data_url() {
+            Ok(url) => self.frames.push(url),
+            Err(e) => {
+                logging::warn(&format!("frame dump read-back failed: {:?}", e));
+                self.finish();
+                return;
+            }
+        }
+        self.update_counter();
+        if self.frames.len() as u32 >= self.target {
+            self.finish();
+        }
+    }
+
+    fn update_counter(&self) {
+        if let Some(counter) = &self.counter {
+            counter.set_text_content(Some(&format!(
+                "\u{25cf} {}/{}",
+                self.frames.len(),
+                self.target
+            )));
+        }
+    }
+
+    fn finish(&mut self) {
+        if let Some(counter) = self.counter.take() {
+            counter.remove();
+        }
+        let frames = std::mem::take(&mut self.frames);
+        self.target = 0;
+        if let Err(e) = download_all(&frames) {
+            logging::warn(&format!("frame dump download failed: {:?}", e));
+        }
+    }
+}
+
+fn show_counter() -> Option<Element> {
+    let document = web_sys::window()?.document()?;
+    let counter = document.create_element("div").ok()?;
+    counter.set_class_name("capture-counter");
+    document.body()?.append_child(&counter).ok()?;
+    Some(counter)
+}
+
+/// Offer each frame as `frame-0001.png`, `frame-0002.png`, ...
+fn download_all(frames: &[String]) -> Result<(), JsValue> {
+    let document = web_sys::window()
+        .and_then(|w| w.document())
+        .ok_or_else(|| JsValue::from_str("no document"))?;
+    for (i, url) in frames.iter().enumerate() {
+        let link: HtmlAnchorElement = document.create_element("a")?.dyn_into()?;
+        link.set_href(url);
+        link.set_download(&format!("frame-{:04}.png", i + 1));
+        link.click();
+    }
+    logging::info(&format!("frame dump: {} frames", frames.len()));
+    Ok(())
+}
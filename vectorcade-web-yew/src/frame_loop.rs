@@ -0,0 +1,76 @@
+//! A cancellable `requestAnimationFrame` loop.
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// Runs a callback every animation frame until stopped or dropped.
+pub struct FrameLoop {
+    inner: Rc<Inner>,
+}
+
+struct Inner {
+    handle: Cell<Option<i32>>,
+    stopped: Cell<bool>,
+    callback: RefCell<Option<Closure<dyn FnMut(f64)>>>,
+}
+
+impl FrameLoop {
+    /// Start calling `on_frame` with the frame timestamp (ms).
+    pub fn start(mut on_frame: impl FnMut(f64) + 'static) -> Self {
+        let inner = Rc::new(Inner {
+            handle: Cell::new(None),
+            stopped: Cell::new(false),
+            callback: RefCell::new(None),
+        });
+        // The closure only holds a weak reference, so dropping the
+        // `FrameLoop` frees it instead of leaking a cycle.
+        let weak = Rc::downgrade(&inner);
+        *inner.callback.borrow_mut() = Some(Closure::new(move |timestamp: f64| {
+            let Some(inner) = weak.upgrade() else {
+                return;
+            };
+            inner.handle.set(None);
+            on_frame(timestamp);
+            inner.schedule();
+        }));
+        inner.schedule();
+        Self { inner }
+    }
+
+    /// Cancel the pending frame; the callback will not run again.
+    pub fn stop(&self) {
+        self.inner.stopped.set(true);
+        if let Some(handle) = self.inner.handle.take() {
+            if let Some(window) = web_sys::window() {
+                let _ = window.cancel_animation_frame(handle);
+            }
+        }
+    }
+}
+
+impl Drop for FrameLoop {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+impl Inner {
+    fn schedule(&self) {
+        if self.stopped.get() {
+            return;
+        }
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        if let Some(callback) = self.callback.borrow().as_ref() {
+            self.handle.set(
+                window
+                    .request_animation_frame(callback.as_ref().unchecked_ref())
+                    .ok(),
+            );
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Keyboard game switcher: PageUp/PageDown browse the game list with
+//! arcade-style hold-to-repeat, and releasing the key starts the
+//! highlighted game.
+
+use crate::repeat::{HoldRepeat, RepeatRate};
+
+/// Browse repeat: a 400 ms delay, then 150 ms speeding up to 60 ms.
+const REPEAT: RepeatRate = RepeatRate {
+    delay_ms: 400.0,
+    interval_ms: 150.0,
+    step_ms: 15.0,
+    min_ms: 60.0,
+};
+
+/// Highlight state while a browse key is held.
+pub struct GameMenu {
+    /// Held direction (-1 previous, +1 next) and highlighted game index.
+    browsing: Option<(isize, usize)>,
+    repeat: HoldRepeat,
+}
+
+impl Default for GameMenu {
+    fn default() -> Self {
+        Self {
+            browsing: None,
+            repeat: HoldRepeat::new(REPEAT),
+        }
+    }
+}
+
+impl GameMenu {
+    /// Browse direction for a key code, if it is a browse key.
+    pub fn direction(code: &str) -> Option<isize> {
+        match code {
+            "PageUp" => Some(-1),
+            "PageDown" => Some(1),
+            _ => None,
+        }
+    }
+
+    /// Begin browsing from `current`; the highlight moves immediately.
+    /// Returns the highlighted index.
+    pub fn press(&mut self, dir: isize, current: usize, count: usize, now: f64) -> usize {
+        let start = self.browsing.map_or(current, |(_, idx)| idx);
+        let idx = step(start, dir, count);
+        self.browsing = Some((dir, idx));
+        self.repeat.press(now);
+        idx
+    }
+
+    /// Highlighted game while browsing.
+    pub fn highlighted(&self) -> Option<usize> {
+        self.browsing.map(|(_, idx)| idx)
+    }
+
+    /// Apply due repeats. Returns the new highlight if it moved.
+    pub fn poll(&mut self, count: usize, now: f64) -> Option<usize> {
+        let (dir, idx) = self.browsing?;
+        let steps = self.repeat.poll(now);
+        if steps == 0 {
+            return None;
+        }
+        let idx = (0..steps).fold(idx, |i, _| step(i, dir, count));
+        self.browsing = Some((dir, idx));
+        Some(idx)
+    }
+
+    /// Stop browsing on release of a key in `dir`; returns the game to start.
+    pub fn release(&mut self, dir: isize) -> Option<usize> {
+        let (held, idx) = self.browsing?;
+        if held != dir {
+            return None;
+        }
+        self.browsing = None;
+        self.repeat.release();
+        Some(idx)
+    }
+}
+
+/// Move one entry in `dir`, wrapping around the list.
+fn step(idx: usize, dir: isize, count: usize) -> usize {
+    if count == 0 {
+        return 0;
+    }
+    (idx as isize + dir).rem_euclid(count as isize) as usize
+}
@@ -0,0 +1,47 @@
+//! Touch swipe and tap recognition mapped to directional key presses.
+
+use glam::Vec2;
+use vectorcade_shared::input::Key;
+
+/// Taps longer than this are treated as holds, not fire (ms).
+const TAP_MAX_MS: f64 = 300.0;
+
+/// Tracks one touch from pointerdown to pointerup.
+#[derive(Default)]
+pub struct SwipeDetector {
+    start: Option<(Vec2, f64)>,
+}
+
+impl SwipeDetector {
+    pub fn begin(&mut self, pos: Vec2, now: f64) {
+        self.start = Some((pos, now));
+    }
+
+    pub fn cancel(&mut self) {
+        self.start = None;
+    }
+
+    /// Finish the gesture and return the key it maps to, if any.
+    ///
+    /// Positions are in CSS pixels with Y pointing down. Movement shorter
+    /// than `threshold` is a tap (fire); otherwise the dominant axis picks
+    /// the direction.
+    pub fn end(&mut self, pos: Vec2, now: f64, threshold: f32) -> Option<Key> {
+        let (start, started_at) = self.start.take()?;
+        let delta = pos - start;
+        if delta.length() < threshold {
+            return (now - started_at <= TAP_MAX_MS).then_some(Key::Space);
+        }
+        Some(if delta.x.abs() > delta.y.abs() {
+            if delta.x > 0.0 {
+                Key::Right
+            } else {
+                Key::Left
+            }
+        } else if delta.y > 0.0 {
+            Key::Down
+        } else {
+            Key::Up
+        })
+    }
+}
@@ -0,0 +1,69 @@
+//! Rumble feedback on gamepads, falling back to phone vibration.
+//!
+//! `vibrationActuator.playEffect` is not in stable `web-sys`, so it is
+//! called through `Reflect`.
+
+use js_sys::{Function, Object, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::{logging, GAME_STATE};
+
+/// Rumble the first connected gamepad for `duration_ms` at `intensity`
+/// (0..1). Without a rumble-capable pad, vibrate the device instead.
+pub fn rumble(intensity: f32, duration_ms: f64) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let intensity = intensity.clamp(0.0, 1.0) as f64;
+    if intensity == 0.0 || duration_ms <= 0.0 {
+        return;
+    }
+    let navigator = window.navigator();
+    if !play_gamepad_effect(&navigator, intensity, duration_ms) {
+        // Phones cannot vary strength, only length.
+        navigator.vibrate_with_duration(duration_ms as u32);
+    }
+}
+
+/// Returns true if a connected gamepad accepted the effect.
+fn play_gamepad_effect(navigator: &web_sys::Navigator, intensity: f64, duration_ms: f64) -> bool {
+    let Ok(pads) = navigator.get_gamepads() else {
+        return false;
+    };
+    let Some(pad) = pads
+        .iter()
+        .find_map(|p| p.dyn_into::<web_sys::Gamepad>().ok())
+    else {
+        return false;
+    };
+    let Ok(actuator) = Reflect::get(&pad, &"vibrationActuator".into()) else {
+        return false;
+    };
+    let Some(play) = Reflect::get(&actuator, &"playEffect".into())
+        .ok()
+        .and_then(|f| f.dyn_into::<Function>().ok())
+    else {
+        return false;
+    };
+    let params = Object::new();
+    let _ = Reflect::set(&params, &"duration".into(), &duration_ms.into());
+    let _ = Reflect::set(&params, &"strongMagnitude".into(), &intensity.into());
+    let _ = Reflect::set(&params, &"weakMagnitude".into(), &intensity.into());
+    match play.call2(&actuator, &"dual-rumble".into(), &params) {
+        Ok(_) => true,
+        Err(e) => {
+            logging::debug(&format!("gamepad rumble failed: {:?}", e));
+            false
+        }
+    }
+}
+
+/// Rumble for the primary game; intended for host pages and future game hooks.
+#[wasm_bindgen]
+pub fn request_rumble(intensity: f32, duration_ms: f64) {
+    let enabled = GAME_STATE.with(|state| state.borrow().settings.haptics);
+    if enabled {
+        rumble(intensity, duration_ms);
+    }
+}
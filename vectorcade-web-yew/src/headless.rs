@@ -0,0 +1,41 @@
+//! Off-DOM game stepping for reproducible tests and previews.
+//!
+//! Games run here exactly as in `GameState::tick`'s update loop (fixed
+//! `TIMESTEP` steps), but with a seeded RNG, no input, and silent audio, so
+//! the same seed and tick count always yield the same frame.
+
+use vectorcade_shared::draw::DrawCmd;
+use vectorcade_shared::game::{Game, GameCtx, ScreenInfo};
+use vectorcade_shared::Xorshift64;
+
+use crate::input::KeyboardInput;
+use crate::{WebAudio, TIMESTEP};
+
+/// Reset `game` with `seed`, step it `ticks` times with no input on a
+/// `screen`-sized display, and return the frame it renders last.
+pub fn run_headless(
+    game: &mut dyn Game,
+    screen: ScreenInfo,
+    seed: u64,
+    ticks: usize,
+) -> Vec<DrawCmd> {
+    // No keys are ever pressed, so every query reads as released.
+    let input = KeyboardInput::default();
+    let audio = WebAudio;
+    let mut rng = Xorshift64::new(seed);
+    let mut ctx = GameCtx {
+        input: &input,
+        audio: &audio,
+        rng: &mut rng,
+        screen,
+        now_s: 0.0,
+    };
+    game.reset(&mut ctx);
+    for tick in 0..ticks {
+        ctx.now_s = tick as f64 * TIMESTEP as f64;
+        game.update(&mut ctx, TIMESTEP);
+    }
+    let mut cmds = Vec::new();
+    game.render(&mut ctx, &mut cmds);
+    cmds
+}
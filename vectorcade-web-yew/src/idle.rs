@@ -0,0 +1,32 @@
+//! Idle dimming for unattended instances.
+//!
+//! After a stretch with no input the frame fades toward black and the render
+//! rate drops; any input wakes it at once. Unlike attract mode nothing plays.
+
+/// Render rate while fully dimmed.
+pub const SLEEP_HZ: f64 = 5.0;
+
+#[derive(Default)]
+pub struct Idle {
+    last_input: f64,
+}
+
+impl Idle {
+    /// Note input at `now` (ms).
+    pub fn input(&mut self, now: f64) {
+        self.last_input = now;
+    }
+
+    /// Frame brightness in `[0, 1]`: full until `dim_after_s` without input,
+    /// then fading to black over `fade_s`. `dim_after_s <= 0` disables dimming.
+    pub fn brightness(&mut self, now: f64, dim_after_s: f32, fade_s: f32) -> f32 {
+        if self.last_input == 0.0 {
+            self.last_input = now;
+        }
+        if dim_after_s <= 0.0 {
+            return 1.0;
+        }
+        let idle_s = ((now - self.last_input) / 1000.0) as f32 - dim_after_s;
+        (1.0 - idle_s / fade_s.max(0.1)).clamp(0.0, 1.0)
+    }
+}
@@ -0,0 +1,139 @@
+//! Arcade high-score initials entry, drawn in vector text.
+//!
+//! Up/down change the current letter (holding repeats), left/right move
+//! between letters, and Enter moves on (finishing on the last letter).
+
+use glam::Vec2;
+use vectorcade_shared::draw::DrawCmd;
+use vectorcade_shared::game::ScreenInfo;
+use vectorcade_shared::input::{InputState, Key};
+use vectorcade_shared::Rgba;
+use wasm_bindgen::prelude::*;
+
+use crate::input::KeyboardInput;
+use crate::repeat::{HoldRepeat, RepeatRate};
+use crate::{overlay, GAME_STATE};
+
+/// Letters cycled through for each slot.
+const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789 ";
+const SLOTS: usize = 3;
+/// Up/down repeat every 100 ms after a 400 ms hold.
+const REPEAT: RepeatRate = RepeatRate::steady(400.0, 100.0);
+
+/// A high score waiting for the player's initials.
+pub struct InitialsEntry {
+    pub score: u64,
+    /// Index into `ALPHABET` for each slot.
+    letters: [usize; SLOTS],
+    cursor: usize,
+    /// Repeat timers for up and down.
+    repeats: [HoldRepeat; 2],
+}
+
+impl InitialsEntry {
+    pub fn new(score: u64) -> Self {
+        Self {
+            score,
+            letters: [0; SLOTS],
+            cursor: 0,
+            repeats: [HoldRepeat::new(REPEAT), HoldRepeat::new(REPEAT)],
+        }
+    }
+
+    /// Keys left to the keyboard state so `cycle` can repeat them.
+    pub fn is_cycle_key(code: &str) -> bool {
+        matches!(code, "ArrowUp" | "KeyW" | "ArrowDown" | "KeyS")
+    }
+
+    /// Change the current letter on up/down presses and their repeats.
+    pub fn cycle(&mut self, keyboard: &KeyboardInput) {
+        let n = ALPHABET.len();
+        let now = keyboard.clock_ms();
+        let letter = &mut self.letters[self.cursor];
+        let dirs = [([Key::Up, Key::W], 1), ([Key::Down, Key::S], n - 1)];
+        for ((keys, delta), repeat) in dirs.into_iter().zip(&mut self.repeats) {
+            let steps = if keys.iter().any(|&k| keyboard.pressed(k)) {
+                repeat.press(now);
+                1
+            } else if keys.iter().any(|&k| keyboard.key(k).is_down) {
+                repeat.poll(now) as usize
+            } else {
+                repeat.release();
+                0
+            };
+            *letter = (*letter + delta * steps) % n;
+        }
+    }
+
+    /// Apply a key. Returns the initials once the player confirms the last
+    /// slot.
+    pub fn key(&mut self, code: &str) -> Option<String> {
+        match code {
+            "ArrowLeft" | "KeyA" => self.cursor = self.cursor.saturating_sub(1),
+            "ArrowRight" | "KeyD" => self.cursor = (self.cursor + 1).min(SLOTS - 1),
+            "Enter" if self.cursor + 1 < SLOTS => self.cursor += 1,
+            "Enter" => return Some(self.initials()),
+            _ => {}
+        }
+        None
+    }
+
+    fn initials(&self) -> String {
+        self.letters.iter().map(|&i| ALPHABET[i] as char).collect()
+    }
+
+    pub fn draw(&self, now: f64, screen: &ScreenInfo, out: &mut Vec<DrawCmd>) {
+        let color = Rgba(0.4, 1.0, 1.0, 1.0);
+        overlay::text_centered(
+            out,
+            screen,
+            Vec2::new(0.0, 0.4),
+            "NEW HIGH SCORE",
+            28.0,
+            color,
+        );
+        let score = self.score.to_string();
+        overlay::text_centered(out, screen, Vec2::new(0.0, 0.25), &score, 28.0, color);
+        overlay::text_centered(
+            out,
+            screen,
+            Vec2::new(0.0, 0.05),
+            "ENTER YOUR INITIALS",
+            20.0,
+            color,
+        );
+
+        let size = 48.0;
+        let advance = overlay::text_width(screen, "W ", size);
+        let left = -advance * (SLOTS as f32 - 1.0) * 0.5;
+        // The slot being edited blinks.
+        let blink_on = (now / 250.0) as u64 % 2 == 0;
+        for (slot, &i) in self.letters.iter().enumerate() {
+            let center = Vec2::new(left + advance * slot as f32, -0.2);
+            let glyph = (ALPHABET[i] as char).to_string();
+            let editing = slot == self.cursor;
+            if !editing || blink_on {
+                overlay::text_centered(out, screen, center, &glyph, size, color);
+            }
+            if editing {
+                let half = overlay::text_width(screen, "W", size) * 0.5;
+                let y = center.y - 0.05;
+                overlay::line(
+                    out,
+                    Vec2::new(center.x - half, y),
+                    Vec2::new(center.x + half, y),
+                    color,
+                    2.0,
+                );
+            }
+        }
+    }
+}
+
+/// Report the primary game's final score. A new best opens initials entry.
+///
+/// Stands in for a game-reported score until games provide one.
+#[wasm_bindgen]
+pub fn report_score(score: f64) {
+    GAME_STATE.with(|state| state.borrow_mut().report_score(score.max(0.0) as u64));
+}
@@ -0,0 +1,491 @@
+//! Browser input mapped onto the shared `InputState` trait.
+//!
+//! Each device (keyboard, gamepad, pointer) is its own `InputState` source.
+//! `CompositeInput` merges them into the single view games see, so adding a
+//! device means implementing `InputState` and listing it in
+//! `WebInput::composite`.
+
+use std::collections::{HashMap, HashSet};
+
+use glam::Vec2;
+use wasm_bindgen::JsCast;
+
+use vectorcade_shared::input::{Axis, Button, InputState, Key, Pointer};
+
+use crate::settings::{KeyMapping, Settings};
+
+/// Raw analog axis values, as reported by stick-style devices.
+#[derive(Clone, Copy, Default)]
+pub struct AnalogAxes {
+    pub move_x: f32,
+    pub move_y: f32,
+    pub thrust: f32,
+}
+
+/// All input devices the platform tracks.
+#[derive(Default)]
+pub struct WebInput {
+    pub keyboard: KeyboardInput,
+    pub gamepad: GamepadInput,
+    pub pointer: PointerInput,
+}
+
+impl WebInput {
+    pub fn with_settings(settings: &Settings) -> Self {
+        let mut input = Self::default();
+        input.apply_settings(settings);
+        input
+    }
+
+    /// Merged view of every device, handed to games through `GameCtx`.
+    pub fn composite(&self) -> CompositeInput<'_> {
+        CompositeInput::new(vec![&self.keyboard, &self.gamepad, &self.pointer])
+    }
+
+    /// Pick up per-device tuning from settings.
+    pub fn apply_settings(&mut self, settings: &Settings) {
+        self.gamepad.deadzone = settings.deadzone;
+        self.gamepad.curve_exponent = settings.curve_exponent;
+    }
+
+    pub fn end_frame(&mut self) {
+        self.keyboard.end_frame();
+    }
+
+    /// Whether any device saw input since the last call.
+    pub fn take_activity(&mut self) -> bool {
+        let keyboard = std::mem::take(&mut self.keyboard.activity);
+        let pointer = std::mem::take(&mut self.pointer.activity);
+        keyboard | pointer | self.gamepad.is_active()
+    }
+
+    /// Map a key event using physical positions (`code`) or the labels on
+    /// the user's layout (`key`), per `mapping`.
+    pub fn map_event(e: &web_sys::KeyboardEvent, mapping: KeyMapping) -> Option<Key> {
+        match mapping {
+            KeyMapping::Physical => Self::map_code(&e.code()),
+            KeyMapping::Logical => Self::map_key(&e.key().to_lowercase()),
+        }
+    }
+
+    /// Map a lowercased `KeyboardEvent.key` value.
+    pub fn map_key(key: &str) -> Option<Key> {
+        match key {
+            "arrowleft" => Some(Key::Left),
+            "arrowright" => Some(Key::Right),
+            "arrowup" => Some(Key::Up),
+            "arrowdown" => Some(Key::Down),
+            "w" => Some(Key::W),
+            "a" => Some(Key::Left),
+            "s" => Some(Key::S),
+            "d" => Some(Key::Right),
+            " " => Some(Key::Space),
+            "enter" => Some(Key::Enter),
+            "escape" => Some(Key::Escape),
+            "z" => Some(Key::Z),
+            "x" => Some(Key::X),
+            "c" => Some(Key::C),
+            _ => None,
+        }
+    }
+
+    pub fn map_code(code: &str) -> Option<Key> {
+        match code {
+            "ArrowLeft" => Some(Key::Left),
+            "ArrowRight" => Some(Key::Right),
+            "ArrowUp" => Some(Key::Up),
+            "ArrowDown" => Some(Key::Down),
+            "KeyW" => Some(Key::W),
+            "KeyA" => Some(Key::Left),
+            "KeyS" => Some(Key::S),
+            "KeyD" => Some(Key::Right),
+            "Space" => Some(Key::Space),
+            "Enter" => Some(Key::Enter),
+            "Escape" => Some(Key::Escape),
+            "KeyZ" => Some(Key::Z),
+            "KeyX" => Some(Key::X),
+            "KeyC" => Some(Key::C),
+            _ => None,
+        }
+    }
+}
+
+/// A button that is up and has no edges this frame.
+fn released() -> Button {
+    Button {
+        is_down: false,
+        went_down: false,
+        went_up: false,
+    }
+}
+
+/// Keyboard state, plus synthetic presses from gestures.
+#[derive(Default)]
+pub struct KeyboardInput {
+    keys: HashMap<Key, bool>,
+    prev_keys: HashMap<Key, bool>,
+    /// Synthetic presses (from gestures) and when to release them (ms).
+    pulses: Vec<(Key, f64)>,
+    /// A key went down since activity was last taken.
+    activity: bool,
+    /// Keys that went down since the last `advance`, however briefly held.
+    presses: HashSet<Key>,
+    /// Keys that went down during the last step, for `pressed`.
+    step_presses: HashSet<Key>,
+    /// Game clock (ms), moved on by `advance`.
+    clock_ms: f64,
+}
+
+impl KeyboardInput {
+    pub fn set_key(&mut self, key: Key, down: bool) {
+        let was_down = self.keys.insert(key, down).unwrap_or(false);
+        if down && !was_down {
+            self.presses.insert(key);
+        }
+        self.activity |= down;
+    }
+
+    /// Press `key` now and release it automatically at `release_at` (ms).
+    pub fn pulse_key(&mut self, key: Key, release_at: f64) {
+        self.set_key(key, true);
+        self.pulses.push((key, release_at));
+    }
+
+    /// Release synthetic presses whose time is up.
+    pub fn release_pulses(&mut self, now: f64) {
+        let mut released = Vec::new();
+        self.pulses.retain(|&(key, release_at)| {
+            let due = now >= release_at;
+            if due {
+                released.push(key);
+            }
+            !due
+        });
+        for key in released {
+            self.set_key(key, false);
+        }
+    }
+
+    pub fn end_frame(&mut self) {
+        self.prev_keys = self.keys.clone();
+    }
+
+    /// Move the game clock on by `dt` seconds and start a new step.
+    pub fn advance(&mut self, dt: f32) {
+        self.clock_ms += dt as f64 * 1000.0;
+        self.step_presses = std::mem::take(&mut self.presses);
+    }
+
+    /// Game clock (ms), for hold-to-repeat timers that stop with the game.
+    pub fn clock_ms(&self) -> f64 {
+        self.clock_ms
+    }
+
+    /// `k` went down during the last step. Unlike `went_down`, this catches
+    /// a tap released before the step, and a re-press while still counted
+    /// as held.
+    pub fn pressed(&self, k: Key) -> bool {
+        self.step_presses.contains(&k)
+    }
+
+    fn digital(&self, k: Key) -> f32 {
+        if self.key(k).is_down {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
+
+impl InputState for KeyboardInput {
+    fn key(&self, k: Key) -> Button {
+        let is_down = *self.keys.get(&k).unwrap_or(&false);
+        let was_down = *self.prev_keys.get(&k).unwrap_or(&false);
+        Button {
+            is_down,
+            went_down: is_down && !was_down,
+            went_up: !is_down && was_down,
+        }
+    }
+
+    /// Keyboard axes are digital: -1, 0, or +1.
+    fn axis(&self, a: Axis) -> f32 {
+        match a {
+            Axis::MoveX => self.digital(Key::Right) - self.digital(Key::Left),
+            Axis::MoveY => self.digital(Key::Up) - self.digital(Key::Down),
+            Axis::Thrust => self.digital(Key::Up).max(self.digital(Key::W)),
+            _ => 0.0,
+        }
+    }
+
+    fn pointer(&self) -> Option<Pointer> {
+        None
+    }
+}
+
+/// Analog stick input with deadzone and response-curve shaping.
+#[derive(Default)]
+pub struct GamepadInput {
+    analog: AnalogAxes,
+    deadzone: f32,
+    curve_exponent: f32,
+}
+
+impl GamepadInput {
+    /// Store raw analog values; shaping happens when games read them.
+    pub fn set_analog(&mut self, analog: AnalogAxes) {
+        self.analog = analog;
+    }
+
+    /// The stick is deflected past the deadzone.
+    fn is_active(&self) -> bool {
+        [Axis::MoveX, Axis::MoveY, Axis::Thrust]
+            .into_iter()
+            .any(|a| self.axis(a) != 0.0)
+    }
+}
+
+impl InputState for GamepadInput {
+    fn key(&self, _k: Key) -> Button {
+        released()
+    }
+
+    fn axis(&self, a: Axis) -> f32 {
+        let stick = || {
+            let raw = Vec2::new(self.analog.move_x, self.analog.move_y);
+            shape_stick(raw, self.deadzone, self.curve_exponent)
+        };
+        match a {
+            Axis::MoveX => stick().x,
+            Axis::MoveY => stick().y,
+            Axis::Thrust => shape_axis(self.analog.thrust, self.deadzone, self.curve_exponent),
+            _ => 0.0,
+        }
+    }
+
+    fn pointer(&self) -> Option<Pointer> {
+        None
+    }
+}
+
+/// Mouse/touch/pen position over the canvas.
+#[derive(Default)]
+pub struct PointerInput {
+    /// Latest pointer position in canvas pixels, and whether it is pressed.
+    pointer_px: Option<(Vec2, bool)>,
+    /// `pointer_px` mapped into game NDC by the view transform.
+    pointer_ndc: Option<(Vec2, bool)>,
+    /// The pointer moved or changed since activity was last taken.
+    activity: bool,
+    /// Pointer lock is active: relative motion drives `aim` instead.
+    locked: bool,
+    /// Virtual aim point (NDC) moved by relative motion while locked.
+    aim: Vec2,
+    aim_down: bool,
+}
+
+impl PointerInput {
+    /// Record the pointer in backing-store pixels (`None` when it leaves).
+    pub fn set_pointer_px(&mut self, pointer: Option<(Vec2, bool)>) {
+        self.activity |= pointer.is_some();
+        self.pointer_px = pointer;
+    }
+
+    /// Enter or leave pointer lock. Aim starts where the pointer was, so
+    /// locking doesn't jump.
+    pub fn set_locked(&mut self, locked: bool) {
+        if locked && !self.locked {
+            self.aim = self.pointer_ndc.map_or(Vec2::ZERO, |(pos, _)| pos);
+        }
+        self.locked = locked;
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Move the aim point by a relative delta in NDC, clamped to the screen.
+    pub fn move_aim(&mut self, delta: Vec2, down: bool) {
+        self.aim = (self.aim + delta).clamp(Vec2::splat(-1.0), Vec2::splat(1.0));
+        self.aim_down = down;
+        self.activity = true;
+    }
+
+    /// Map the pixel pointer into game space with the view's inverse
+    /// transform, so games receive coordinates in their own NDC.
+    pub fn resolve_pointer(&mut self, to_ndc: impl Fn(Vec2) -> Vec2) {
+        self.pointer_ndc = self.pointer_px.map(|(pos, down)| (to_ndc(pos), down));
+    }
+}
+
+impl InputState for PointerInput {
+    fn key(&self, _k: Key) -> Button {
+        released()
+    }
+
+    fn axis(&self, _a: Axis) -> f32 {
+        0.0
+    }
+
+    fn pointer(&self) -> Option<Pointer> {
+        if self.locked {
+            return Some(Pointer {
+                pos: self.aim,
+                down: self.aim_down,
+            });
+        }
+        self.pointer_ndc.map(|(pos, down)| Pointer { pos, down })
+    }
+}
+
+/// Merges several `InputState` sources into one.
+///
+/// Keys are down if any source has them down, with edges computed from the
+/// combined state so two devices holding the same key don't double-fire.
+/// Axes report the source with the largest magnitude.
+pub struct CompositeInput<'a> {
+    sources: Vec<&'a dyn InputState>,
+}
+
+impl<'a> CompositeInput<'a> {
+    pub fn new(sources: Vec<&'a dyn InputState>) -> Self {
+        Self { sources }
+    }
+}
+
+impl InputState for CompositeInput<'_> {
+    fn key(&self, k: Key) -> Button {
+        let mut is_down = false;
+        let mut was_down = false;
+        for source in &self.sources {
+            let b = source.key(k);
+            is_down |= b.is_down;
+            // Reconstruct the source's previous state from its edges.
+            was_down |= (b.is_down && !b.went_down) || b.went_up;
+        }
+        Button {
+            is_down,
+            went_down: is_down && !was_down,
+            went_up: !is_down && was_down,
+        }
+    }
+
+    fn axis(&self, a: Axis) -> f32 {
+        self.sources.iter().map(|s| s.axis(a)).fold(0.0, |best, v| {
+            if v.abs() > best.abs() {
+                v
+            } else {
+                best
+            }
+        })
+    }
+
+    fn pointer(&self) -> Option<Pointer> {
+        self.sources.iter().find_map(|s| s.pointer())
+    }
+}
+
+/// Read the left stick of the first connected gamepad, if any.
+pub fn poll_gamepad() -> Option<AnalogAxes> {
+    let navigator = web_sys::window()?.navigator();
+    let pads = navigator.get_gamepads().ok()?;
+    let pad = pads
+        .iter()
+        .find_map(|p| p.dyn_into::<web_sys::Gamepad>().ok())?;
+    let axes = pad.axes();
+    let x = axes.get(0).as_f64().unwrap_or(0.0) as f32;
+    let y = axes.get(1).as_f64().unwrap_or(0.0) as f32;
+    // Gamepad Y points down; games expect up to be positive.
+    Some(AnalogAxes {
+        move_x: x,
+        move_y: -y,
+        thrust: (-y).max(0.0),
+    })
+}
+
+/// Apply a deadzone and power response curve to a magnitude in `0..`.
+///
+/// The live range above the deadzone is rescaled to `0..1` before the curve
+/// so there is no jump at the deadzone edge.
+fn shape_magnitude(magnitude: f32, deadzone: f32, exponent: f32) -> f32 {
+    if magnitude <= deadzone {
+        return 0.0;
+    }
+    let live = (1.0 - deadzone).max(f32::EPSILON);
+    let t = ((magnitude - deadzone) / live).min(1.0);
+    t.powf(exponent.max(1.0))
+}
+
+/// Shape a single axis (a trigger, or thrust); the result is in `[-1, 1]`.
+pub fn shape_axis(raw: f32, deadzone: f32, exponent: f32) -> f32 {
+    shape_magnitude(raw.abs(), deadzone, exponent) * raw.signum()
+}
+
+/// Shape a stick with a radial deadzone: the deadzone and curve apply to
+/// the stick's distance from center and the direction is kept, so
+/// diagonals don't snap to the axes. The result is within the unit circle.
+pub fn shape_stick(raw: Vec2, deadzone: f32, exponent: f32) -> Vec2 {
+    raw.normalize_or_zero() * shape_magnitude(raw.length(), deadzone, exponent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tap_between_steps_counts_as_a_press() {
+        let mut keyboard = KeyboardInput::default();
+        keyboard.advance(0.016);
+        keyboard.set_key(Key::Up, true);
+        keyboard.set_key(Key::Up, false);
+        keyboard.advance(0.016);
+        assert!(keyboard.pressed(Key::Up));
+        assert!(!keyboard.key(Key::Up).is_down);
+        keyboard.advance(0.016);
+        assert!(!keyboard.pressed(Key::Up));
+    }
+
+    #[test]
+    fn re_press_within_a_step_is_a_fresh_press() {
+        let mut keyboard = KeyboardInput::default();
+        keyboard.set_key(Key::Up, true);
+        keyboard.advance(0.016);
+        keyboard.advance(0.016);
+        assert!(!keyboard.pressed(Key::Up));
+        keyboard.set_key(Key::Up, false);
+        keyboard.set_key(Key::Up, true);
+        keyboard.advance(0.016);
+        assert!(keyboard.pressed(Key::Up));
+        // Repeated keydowns while held are not presses.
+        keyboard.set_key(Key::Up, true);
+        keyboard.advance(0.016);
+        assert!(!keyboard.pressed(Key::Up));
+    }
+
+    #[test]
+    fn diagonal_stick_keeps_its_direction() {
+        // Each axis alone is inside the deadzone, but the stick is not.
+        let shaped = shape_stick(Vec2::new(0.2, 0.2), 0.25, 1.0);
+        assert!(shaped.x > 0.0 && shaped.y > 0.0);
+        assert!((shaped.x - shaped.y).abs() < 1e-6);
+
+        let raw = Vec2::new(0.6, 0.3);
+        let shaped = shape_stick(raw, 0.15, 2.0);
+        assert!(shaped.normalize().abs_diff_eq(raw.normalize(), 1e-5));
+    }
+
+    #[test]
+    fn stick_deadzone_is_round() {
+        assert_eq!(shape_stick(Vec2::new(0.1, 0.1), 0.15, 1.0), Vec2::ZERO);
+        assert_eq!(shape_stick(Vec2::new(0.0, -0.15), 0.15, 1.0), Vec2::ZERO);
+        let full = shape_stick(Vec2::new(1.0, 1.0), 0.15, 1.5);
+        assert!((full.length() - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn axis_has_no_jump_at_the_deadzone_edge() {
+        assert_eq!(shape_axis(0.2, 0.2, 1.0), 0.0);
+        assert!(shape_axis(0.21, 0.2, 1.0) < 0.02);
+        assert_eq!(shape_axis(-1.0, 0.2, 2.0), -1.0);
+    }
+}
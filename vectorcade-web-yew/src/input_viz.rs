@@ -0,0 +1,90 @@
+//! On-canvas input visualizer (F3) for streams and "my key isn't working"
+//! reports: shows exactly what the platform passes to the game.
+
+use glam::Vec2;
+use vectorcade_shared::draw::DrawCmd;
+use vectorcade_shared::game::ScreenInfo;
+use vectorcade_shared::input::{Axis, InputState, Key};
+use vectorcade_shared::Rgba;
+
+use crate::overlay;
+
+const LIT: Rgba = Rgba(0.3, 1.0, 0.6, 0.9);
+const DIM: Rgba = Rgba(0.4, 0.4, 0.5, 0.5);
+
+/// Size of one direction/button cell, in pixels.
+const CELL_PX: f32 = 22.0;
+
+/// Draw held directions, action buttons, and the analog stick in the
+/// bottom-left corner.
+pub fn draw(input: &dyn InputState, screen: &ScreenInfo, out: &mut Vec<DrawCmd>) {
+    let cell = CELL_PX * overlay::ndc_per_px(screen);
+    let held = |k: Key| if input.key(k).is_down { LIT } else { DIM };
+
+    // Direction pad: a cross of arrow cells around `pad`.
+    let pad = Vec2::new(-0.95 + 1.5 * cell, -0.95 + 1.5 * cell);
+    for (key, dir) in [
+        (Key::Up, Vec2::Y),
+        (Key::Down, Vec2::NEG_Y),
+        (Key::Left, Vec2::NEG_X),
+        (Key::Right, Vec2::X),
+    ] {
+        arrow(out, pad + dir * cell, dir, cell * 0.4, held(key));
+    }
+
+    // Action buttons in a row to the right of the pad.
+    let mut x = pad.x + 2.0 * cell;
+    for (key, label) in [
+        (Key::Z, "Z"),
+        (Key::X, "X"),
+        (Key::C, "C"),
+        (Key::Space, "SPC"),
+    ] {
+        let width = cell * (label.len() as f32 * 0.5 + 0.5);
+        let min = Vec2::new(x, pad.y - cell * 0.5);
+        let color = held(key);
+        overlay::rect(out, min, min + Vec2::new(width, cell), color, 1.5);
+        overlay::text(
+            out,
+            min + Vec2::splat(cell * 0.25),
+            label,
+            CELL_PX * 0.5,
+            color,
+        );
+        x += width + cell * 0.3;
+    }
+
+    // Analog stick: a ring with a dot at the current axes.
+    let center = Vec2::new(x + cell, pad.y);
+    let radius = cell;
+    let stick = Vec2::new(input.axis(Axis::MoveX), input.axis(Axis::MoveY));
+    let color = if stick != Vec2::ZERO { LIT } else { DIM };
+    overlay::polyline(out, circle(center, radius, 16), true, DIM, 1.0);
+    overlay::polyline(
+        out,
+        circle(center + stick * radius, radius * 0.2, 8),
+        true,
+        color,
+        2.0,
+    );
+}
+
+/// A filled-looking triangle pointing along `dir`.
+fn arrow(out: &mut Vec<DrawCmd>, center: Vec2, dir: Vec2, size: f32, color: Rgba) {
+    let side = dir.perp();
+    let pts = vec![
+        center + dir * size,
+        center - dir * size + side * size,
+        center - dir * size - side * size,
+    ];
+    overlay::polyline(out, pts, true, color, 2.0);
+}
+
+fn circle(center: Vec2, radius: f32, segments: usize) -> Vec<Vec2> {
+    (0..segments)
+        .map(|i| {
+            let t = i as f32 / segments as f32 * std::f32::consts::TAU;
+            center + Vec2::new(t.cos(), t.sin()) * radius
+        })
+        .collect()
+}
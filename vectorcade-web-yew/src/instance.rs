@@ -0,0 +1,96 @@
+//! Independent VectorCade instances, one per canvas.
+//!
+//! The Yew `App` drives the primary instance. Host pages can start more with
+//! `mount(canvas_id)`; each owns its game state, renderer, and frame loop, and
+//! takes keyboard input only while its canvas has focus.
+//!
+//! Mounted instances read the saved settings and stats but never write them
+//! back. The page-level JS hooks (`load_game`, `set_frame_export`,
+//! `set_frame_export_binary`, `request_shake`, `request_zoom`,
+//! `request_rumble`, `report_score`) act on the primary instance only, and the
+//! F10 debug draw toggle is page-wide.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use vectorcade_render_wgpu::WgpuRenderer;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlCanvasElement;
+use yew::Callback;
+
+use crate::frame_loop::FrameLoop;
+use crate::listeners::Listeners;
+use crate::{logging, GameState, GAME_STATE, RENDERER};
+
+/// Game state plus the renderer that draws it.
+#[derive(Clone)]
+pub struct Instance {
+    pub state: Rc<RefCell<GameState>>,
+    pub renderer: Rc<RefCell<Option<WgpuRenderer>>>,
+}
+
+impl Instance {
+    pub fn new() -> Self {
+        Self {
+            state: Rc::new(RefCell::new(GameState::mounted())),
+            renderer: Rc::default(),
+        }
+    }
+
+    /// The instance behind the Yew `App`.
+    pub fn primary() -> Self {
+        Self {
+            state: GAME_STATE.with(Rc::clone),
+            renderer: RENDERER.with(Rc::clone),
+        }
+    }
+}
+
+thread_local! {
+    /// Frame loops and event listeners of mounted instances, keyed by canvas
+    /// id. Together they own the instance; dropping both stops it.
+    static MOUNTED: RefCell<HashMap<String, (FrameLoop, Listeners)>> = RefCell::default();
+}
+
+/// Start an independent instance rendering into `<canvas id=canvas_id>`.
+///
+/// Mounting the same id again replaces the running instance, listeners
+/// included.
+#[wasm_bindgen]
+pub fn mount(canvas_id: String) -> Result<(), JsValue> {
+    let document = web_sys::window()
+        .and_then(|w| w.document())
+        .ok_or_else(|| JsValue::from_str("no document"))?;
+    let canvas: HtmlCanvasElement = document
+        .get_element_by_id(&canvas_id)
+        .ok_or_else(|| JsValue::from_str(&format!("no element #{}", canvas_id)))?
+        .dyn_into()?;
+    // Focusable, so it receives keys only while selected.
+    canvas.set_tab_index(0);
+
+    let instance = Instance::new();
+    let mut listeners = crate::setup_key_listeners(&canvas, &instance.state, true);
+    listeners.extend(crate::setup_pointer_listeners(
+        &canvas,
+        &instance.state,
+        Callback::noop(),
+    ));
+    listeners.extend(crate::setup_context_loss_listeners(
+        &canvas,
+        &instance.renderer,
+        Callback::noop(),
+    ));
+
+    spawn_local(async move {
+        // On failure `listeners` drops here, detaching the new instance.
+        if crate::create_renderer(canvas.clone(), &instance.renderer).await {
+            logging::info(&format!("mounted instance on #{}", canvas_id));
+            let frame_loop = crate::start_animation_loop(canvas, instance);
+            MOUNTED.with(|m| m.borrow_mut().insert(canvas_id, (frame_loop, listeners)));
+        }
+    });
+    Ok(())
+}
@@ -0,0 +1,37 @@
+//! Kiosk mode for always-on cabinets, enabled with `?kiosk=1`.
+//!
+//! In kiosk mode the whole frame drifts a few pixels on a slow orbit so
+//! static bright vectors don't burn in on the display.
+
+use glam::Vec2;
+use vectorcade_shared::draw::DrawCmd;
+use vectorcade_shared::game::ScreenInfo;
+
+use crate::{display_list, overlay, query};
+
+/// Whether the page was opened in kiosk mode.
+pub fn enabled_from_url() -> bool {
+    query::param("kiosk").is_some_and(|v| v == "1" || v == "true")
+}
+
+/// Burn-in offset in pixels at `now_s`: a slow figure-eight of radius
+/// `amount_px` completing once every `period_s`.
+///
+/// The figure-eight (1:2 Lissajous) covers the area around the origin more
+/// evenly than a circle, so no pixel sits at the same offset for long.
+pub fn jitter_offset(now_s: f64, amount_px: f32, period_s: f32) -> Vec2 {
+    let phase = (now_s / period_s.max(1.0) as f64).fract() as f32 * std::f32::consts::TAU;
+    Vec2::new(phase.sin(), (2.0 * phase).sin() * 0.5) * amount_px
+}
+
+/// Shift the whole frame by the burn-in offset.
+pub fn apply_jitter(
+    now_s: f64,
+    amount_px: f32,
+    period_s: f32,
+    screen: &ScreenInfo,
+    cmds: &mut [DrawCmd],
+) {
+    let offset = jitter_offset(now_s, amount_px, period_s) * overlay::ndc_per_px(screen);
+    display_list::map_points(cmds, |p| p + offset);
+}
@@ -0,0 +1,58 @@
+//! DOM event listeners that are removed when dropped.
+//!
+//! Mounted instances keep theirs next to the frame loop, so unmounting or
+//! re-mounting a canvas also stops the old instance hearing input. The
+//! page-level instance lives as long as the page and forgets its own.
+
+use std::any::Any;
+
+use js_sys::Function;
+use wasm_bindgen::closure::WasmClosure;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::EventTarget;
+
+#[derive(Default)]
+pub struct Listeners {
+    added: Vec<(EventTarget, &'static str, Function)>,
+    /// Owns the closures behind `added`.
+    closures: Vec<Box<dyn Any>>,
+}
+
+impl Listeners {
+    /// Call `closure` for each of `events` on `target` while this lives.
+    pub fn listen<T: ?Sized + WasmClosure + 'static>(
+        &mut self,
+        target: &EventTarget,
+        events: &[&'static str],
+        closure: Closure<T>,
+    ) {
+        let function: &Function = closure.as_ref().unchecked_ref();
+        for event in events {
+            target
+                .add_event_listener_with_callback(event, function)
+                .unwrap();
+            self.added.push((target.clone(), event, function.clone()));
+        }
+        self.closures.push(Box::new(closure));
+    }
+
+    /// Take over `other`'s listeners.
+    pub fn extend(&mut self, mut other: Listeners) {
+        self.added.append(&mut other.added);
+        self.closures.append(&mut other.closures);
+    }
+
+    /// Keep the listeners for the rest of the page's life.
+    pub fn forget(self) {
+        std::mem::forget(self);
+    }
+}
+
+impl Drop for Listeners {
+    fn drop(&mut self) {
+        for (target, event, function) in &self.added {
+            let _ = target.remove_event_listener_with_callback(event, function);
+        }
+    }
+}
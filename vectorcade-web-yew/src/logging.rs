@@ -0,0 +1,70 @@
+//! Leveled logging to the browser console.
+//!
+//! A thin facade over `web_sys::console` with a runtime level filter.
+//! The level defaults to `Info` and can be raised with `?log=debug`.
+
+use std::cell::Cell;
+
+use crate::query;
+
+/// Log severity, ordered from most to least verbose.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "debug" => Some(Self::Debug),
+            "info" => Some(Self::Info),
+            "warn" => Some(Self::Warn),
+            "error" => Some(Self::Error),
+            _ => None,
+        }
+    }
+}
+
+thread_local! {
+    static LEVEL: Cell<Level> = const { Cell::new(Level::Info) };
+}
+
+/// Set the level from the `log` URL parameter, if present.
+pub fn init_from_url() {
+    if let Some(level) = query::param("log").and_then(|s| Level::parse(&s)) {
+        LEVEL.with(|l| l.set(level));
+    }
+}
+
+/// Emit `msg` if `level` passes the current filter.
+pub fn log(level: Level, msg: &str) {
+    if level < LEVEL.with(|l| l.get()) {
+        return;
+    }
+    let line = format!("[vectorcade] {}", msg).into();
+    match level {
+        Level::Debug => web_sys::console::debug_1(&line),
+        Level::Info => web_sys::console::info_1(&line),
+        Level::Warn => web_sys::console::warn_1(&line),
+        Level::Error => web_sys::console::error_1(&line),
+    }
+}
+
+pub fn debug(msg: &str) {
+    log(Level::Debug, msg);
+}
+
+pub fn info(msg: &str) {
+    log(Level::Info, msg);
+}
+
+pub fn warn(msg: &str) {
+    log(Level::Warn, msg);
+}
+
+pub fn error(msg: &str) {
+    log(Level::Error, msg);
+}
@@ -3,118 +3,143 @@
 //! This module provides the browser shell that hosts vector arcade games
 //! using wgpu rendering with 4x MSAA and keyboard/touch input.
 
+mod announce;
+mod attract;
+mod audio;
+mod bench;
+mod boot_error;
+mod cmd_stats;
+mod color;
+mod continue_prompt;
+mod crash_guard;
+mod credits;
+mod crossfade;
+mod crosshair;
+mod debug_draw;
+mod dev;
+mod devices;
+mod display_list;
+mod dpr;
+mod drift;
+mod export;
+mod frame_dump;
+mod frame_loop;
+mod game_menu;
+mod gestures;
+mod haptics;
+mod headless;
+mod idle;
+mod initials;
+mod input;
+mod input_viz;
+mod instance;
+mod kiosk;
+mod listeners;
+mod logging;
+mod orientation;
+mod overlay;
+mod overrides;
+mod pad;
+mod pause;
+mod power;
+mod profiles;
+mod quality;
+mod query;
+mod recorder;
+mod refresh;
+mod repeat;
+mod reveal;
+mod scaling;
+mod scripted;
+mod self_test;
+mod settings;
+mod settings_dialog;
+mod spectate;
+mod splash;
+mod stats;
+mod storage;
+mod svg;
+mod text_snap;
+mod thumbnails;
+mod toast;
+mod touch_controls;
+mod tuning;
+mod view;
+mod warmup;
+
 use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 
 // Build info from build.rs
 const BUILD_GIT_SHA: &str = env!("BUILD_GIT_SHA");
 const BUILD_TIMESTAMP: &str = env!("BUILD_TIMESTAMP");
 const BUILD_HOST: &str = env!("BUILD_HOST");
-use std::collections::HashMap;
 use std::rc::Rc;
+
+use glam::Vec2;
+use gloo::timers::callback::Timeout;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::spawn_local;
-use web_sys::{HtmlCanvasElement, KeyboardEvent};
+use web_sys::{HtmlCanvasElement, KeyboardEvent, PointerEvent};
 use yew::prelude::*;
 
 // Note: FontRegistry is now handled internally by WgpuRenderer
-use vectorcade_games::all_games;
 use vectorcade_render_wgpu::{VectorRenderer, WgpuRenderer};
-use vectorcade_shared::draw::DrawCmd;
+use vectorcade_shared::draw::{DrawCmd, Stroke};
 use vectorcade_shared::game::{AudioOut, Game, GameCtx, GameMeta, ScreenInfo};
-use vectorcade_shared::input::{Axis, Button, InputState, Key, Pointer};
-use vectorcade_shared::Xorshift64;
+use vectorcade_shared::input::InputState;
+use vectorcade_shared::{Rgba, Xorshift64};
+
+use announce::Announcer;
+use attract::Attract;
+use bench::Benchmark;
+use cmd_stats::CmdHistogram;
+use continue_prompt::ContinuePrompt;
+use credits::Credits;
+use crossfade::Crossfade;
+use drift::Drift;
+use frame_dump::FrameDump;
+use frame_loop::FrameLoop;
+use game_menu::GameMenu;
+use gestures::SwipeDetector;
+use idle::Idle;
+use initials::InitialsEntry;
+use input::WebInput;
+use instance::Instance;
+use listeners::Listeners;
+use orientation::{Orientation, RotatedInput};
+use overlay::TextDirection;
+use overrides::Overrides;
+use pad::PadButtons;
+use pause::Pause;
+use power::Battery;
+use profiles::GameProfile;
+use quality::AdaptiveGlow;
+use refresh::{FrameLimiter, RefreshMeter};
+use reveal::Reveal;
+use scaling::ViewTransform;
+use self_test::SelfTest;
+use settings::{LetterboxBars, Settings};
+use settings_dialog::SettingsDialog;
+use spectate::{Snapshot, Spectate};
+use splash::Splash;
+use stats::StatsBook;
+use text_snap::TextSnap;
+use toast::Toast;
+use touch_controls::TouchControls;
+use tuning::{Tuning, TuningPanel};
+use view::ViewFx;
+use warmup::WarmUp;
 
 /// Fixed timestep for game updates (60 Hz).
 const TIMESTEP: f32 = 1.0 / 60.0;
 
-/// Keyboard input state tracking.
-#[derive(Default)]
-struct WebInput {
-    keys: HashMap<Key, bool>,
-    prev_keys: HashMap<Key, bool>,
-}
-
-impl WebInput {
-    fn set_key(&mut self, key: Key, down: bool) {
-        self.keys.insert(key, down);
-    }
-
-    fn end_frame(&mut self) {
-        self.prev_keys = self.keys.clone();
-    }
+/// `localStorage` key for the id of the last selected game.
+const LAST_GAME_KEY: &str = "vectorcade.last_game";
 
-    fn map_code(code: &str) -> Option<Key> {
-        match code {
-            "ArrowLeft" => Some(Key::Left),
-            "ArrowRight" => Some(Key::Right),
-            "ArrowUp" => Some(Key::Up),
-            "ArrowDown" => Some(Key::Down),
-            "KeyW" => Some(Key::W),
-            "KeyA" => Some(Key::Left),
-            "KeyS" => Some(Key::S),
-            "KeyD" => Some(Key::Right),
-            "Space" => Some(Key::Space),
-            "Enter" => Some(Key::Enter),
-            "Escape" => Some(Key::Escape),
-            "KeyZ" => Some(Key::Z),
-            "KeyX" => Some(Key::X),
-            "KeyC" => Some(Key::C),
-            _ => None,
-        }
-    }
-}
-
-impl InputState for WebInput {
-    fn key(&self, k: Key) -> Button {
-        let is_down = *self.keys.get(&k).unwrap_or(&false);
-        let was_down = *self.prev_keys.get(&k).unwrap_or(&false);
-        Button {
-            is_down,
-            went_down: is_down && !was_down,
-            went_up: !is_down && was_down,
-        }
-    }
-
-    fn axis(&self, a: Axis) -> f32 {
-        match a {
-            Axis::MoveX => {
-                let left = if self.key(Key::Left).is_down {
-                    -1.0
-                } else {
-                    0.0
-                };
-                let right = if self.key(Key::Right).is_down {
-                    1.0
-                } else {
-                    0.0
-                };
-                left + right
-            }
-            Axis::MoveY => {
-                let up = if self.key(Key::Up).is_down { 1.0 } else { 0.0 };
-                let down = if self.key(Key::Down).is_down {
-                    -1.0
-                } else {
-                    0.0
-                };
-                up + down
-            }
-            Axis::Thrust => {
-                if self.key(Key::Up).is_down || self.key(Key::W).is_down {
-                    1.0
-                } else {
-                    0.0
-                }
-            }
-            _ => 0.0,
-        }
-    }
-
-    fn pointer(&self) -> Option<Pointer> {
-        None // TODO: mouse/touch support
-    }
-}
+/// How long a swipe or tap holds its synthetic key down (ms).
+const SWIPE_PRESS_MS: f64 = 100.0;
 
 /// Stub audio output.
 struct WebAudio;
@@ -122,7 +147,7 @@ impl AudioOut for WebAudio {}
 
 /// Game state held outside Yew for the animation loop.
 struct GameState {
-    games: Vec<Box<dyn Game + Send>>,
+    games: Vec<Box<dyn Game>>,
     selected: usize,
     input: WebInput,
     rng: Xorshift64,
@@ -130,50 +155,453 @@ struct GameState {
     last_time: f64,
     draw_cmds: Vec<DrawCmd>,
     screen: ScreenInfo,
+    /// Settings in effect: `global_settings` under the current game's overrides.
+    settings: Settings,
+    global_settings: Settings,
+    overrides: Overrides,
+    splash: Splash,
+    self_test: SelfTest,
+    show_wireframe: bool,
+    show_input: bool,
+    /// Histogram of the game's commands, while the F4 inspector is on.
+    cmd_histogram: Option<CmdHistogram>,
+    profile: GameProfile,
+    bench: Option<Benchmark>,
+    /// Player has pressed a game key since the last reset.
+    ///
+    /// Stands in for a game-reported "run in progress" flag when deciding
+    /// whether Reset needs confirmation.
+    played_since_reset: bool,
+    toast: Toast,
+    view: ViewFx,
+    refresh: RefreshMeter,
+    limiter: FrameLimiter,
+    adaptive_glow: AdaptiveGlow,
+    swipe: SwipeDetector,
+    credits: Credits,
+    continue_prompt: ContinuePrompt,
+    crossfade: Crossfade,
+    /// New high score awaiting initials.
+    initials: Option<InitialsEntry>,
+    pause: Pause,
+    text_snap: TextSnap,
+    frame_dump: FrameDump,
+    stats: StatsBook,
+    idle: Idle,
+    /// Kiosk showcase; only runs when `kiosk` is set.
+    attract: Attract,
+    warm_up: WarmUp,
+    reveal: Reveal,
+    /// The last frame hit the primitive budget; warnings go out on the edge.
+    over_budget: bool,
+    /// Frames drawn under the beam speed simulation, to rotate its window.
+    beam_frame: u64,
+    menu: GameMenu,
+    /// Told the new game id whenever the selection changes, so the UI can follow.
+    on_game_changed: Option<Callback<&'static str>>,
+    /// Lets the UI hear when `H` shows or hides the chrome.
+    on_chrome_changed: Option<Callback<bool>>,
+    /// Screen reader messages, and where the UI hears them.
+    announcer: Announcer,
+    on_announce: Option<Callback<String>>,
+    /// Live glow tuning; `Some` only with `?dev=1`.
+    tuning: Option<Tuning>,
+    /// Lets the UI hear when F8 shows or hides the tuning panel.
+    on_tuning_toggled: Option<Callback<bool>>,
+    show_tuning: bool,
+    /// Told `true` to stop the frame loop after a long pause and `false` to
+    /// restart it; instances without one keep running.
+    on_sleep: Option<Callback<bool>>,
+    /// `on_sleep` was told to stop the loop.
+    sleeping: bool,
+    /// Last frame as export JSON; `None` while frame export is off.
+    frame_json: Option<String>,
+    /// Last frame in the binary export format; `None` while that is off.
+    frame_binary: Option<Vec<u8>>,
+    /// Opened with `?kiosk=1`: always-on cabinet protections apply.
+    kiosk: bool,
+    /// Skip off-screen primitives before rendering; `?cull=0` turns it off.
+    cull: bool,
+    /// Multi-tab sync role (`?sync=play` or `?sync=watch`).
+    spectate: Option<Spectate>,
+    /// Fixed-step drift diagnostic (`?drift=1`).
+    drift: Option<Drift>,
+    /// `dpr::generation()` as of the last frame.
+    dpr_generation: u32,
+    /// Page URL query parameters, for games to read on reset.
+    params: HashMap<String, String>,
+    battery: Battery,
+    touch_controls: TouchControls,
+    pad_buttons: PadButtons,
+    /// Games whose reset panicked; they stay out of reach for the session.
+    unavailable: HashSet<&'static str>,
+    /// The page's own instance. Only it writes stats, settings and the last
+    /// game to storage or sets the shared audio volumes; mounted instances
+    /// read them and keep their changes to themselves.
+    primary: bool,
 }
 
 impl GameState {
     fn new() -> Self {
+        Self::with_games(scripted::registry(), true)
+    }
+
+    /// State for an instance mounted beside the primary one; see `primary`.
+    fn mounted() -> Self {
+        Self::with_games(scripted::registry(), false)
+    }
+
+    /// State over `games` instead of the registry.
+    fn with_games(games: Vec<Box<dyn Game>>, primary: bool) -> Self {
+        if games.is_empty() {
+            logging::error("no games registered");
+        }
+        let params = query::all();
+        let unavailable: HashSet<&'static str> = crash_guard::crashed_last_load()
+            .and_then(|id| games.iter().map(|g| g.metadata().id).find(|g| *g == id))
+            .into_iter()
+            .collect();
+        // `?game=<id>` wins; otherwise restore the last game by id, so
+        // reordering the registry is harmless.
+        let selected = params
+            .get("game")
+            .cloned()
+            .or_else(|| storage::load::<String>(LAST_GAME_KEY))
+            .and_then(|id| games.iter().position(|g| g.metadata().id == id))
+            .filter(|&idx| !unavailable.contains(games[idx].metadata().id))
+            .or_else(|| {
+                games
+                    .iter()
+                    .position(|g| !unavailable.contains(g.metadata().id))
+            })
+            .unwrap_or(0);
+        let profile = games
+            .get(selected)
+            .map(|g| profiles::profile(g.metadata().id))
+            .unwrap_or_default();
+        let global_settings: Settings = storage::load(settings::STORAGE_KEY).unwrap_or_default();
+        let overrides = Overrides::load();
+        let settings = games.get(selected).map_or_else(
+            || global_settings.clone(),
+            |g| overrides.resolve(&global_settings, g.metadata().id),
+        );
+        let mut stats = StatsBook::load();
+        if primary {
+            audio::set_volumes(&settings);
+            if let Some(game) = games.get(selected) {
+                stats.record_play(game.metadata().id);
+            }
+        } else {
+            stats.detach();
+        }
         Self {
-            games: all_games(),
-            selected: 0,
-            input: WebInput::default(),
+            games,
+            selected,
+            input: WebInput::with_settings(&settings),
             rng: Xorshift64::new(42),
             accumulator: 0.0,
             last_time: 0.0,
             draw_cmds: Vec::with_capacity(1024),
             screen: ScreenInfo::default(),
+            settings,
+            global_settings,
+            overrides,
+            splash: Splash::default(),
+            self_test: SelfTest::default(),
+            show_wireframe: false,
+            show_input: false,
+            cmd_histogram: None,
+            profile,
+            bench: Benchmark::from_url(),
+            played_since_reset: false,
+            toast: Toast::default(),
+            view: ViewFx::default(),
+            refresh: RefreshMeter::default(),
+            limiter: FrameLimiter::default(),
+            adaptive_glow: AdaptiveGlow::default(),
+            swipe: SwipeDetector::default(),
+            credits: Credits::default(),
+            continue_prompt: ContinuePrompt::default(),
+            crossfade: Crossfade::default(),
+            initials: None,
+            pause: Pause::default(),
+            text_snap: TextSnap::default(),
+            frame_dump: FrameDump::default(),
+            stats,
+            idle: Idle::default(),
+            attract: Attract::default(),
+            warm_up: WarmUp::default(),
+            reveal: Reveal::default(),
+            over_budget: false,
+            beam_frame: 0,
+            menu: GameMenu::default(),
+            on_game_changed: None,
+            on_chrome_changed: None,
+            announcer: Announcer::default(),
+            on_announce: None,
+            tuning: Tuning::from_url(),
+            on_tuning_toggled: None,
+            show_tuning: false,
+            on_sleep: None,
+            sleeping: false,
+            frame_json: None,
+            frame_binary: None,
+            kiosk: kiosk::enabled_from_url(),
+            cull: query::param("cull").as_deref() != Some("0"),
+            drift: Drift::from_url(),
+            spectate: Spectate::from_url(),
+            dpr_generation: dpr::generation(),
+            params,
+            battery: Battery::watch(),
+            touch_controls: TouchControls::default(),
+            pad_buttons: PadButtons::default(),
+            unavailable,
+            primary,
+        }
+    }
+
+    /// Replace the global settings and apply them, under the current
+    /// game's overrides.
+    fn set_settings(&mut self, settings: Settings) {
+        if self.primary {
+            storage::save(settings::STORAGE_KEY, &settings);
+        }
+        self.global_settings = settings;
+        self.apply_settings();
+    }
+
+    /// Apply a settings dialog edit: the fields that changed from `prev` to
+    /// `next` go to the global settings, or with `this_game` only to the
+    /// current game's overrides.
+    fn edit_settings(&mut self, prev: &Settings, next: &Settings, this_game: bool) {
+        let changes = overrides::changes(prev, next);
+        if this_game {
+            let id = self.current_game_id();
+            self.overrides.merge(id, changes, &self.global_settings);
+            self.apply_settings();
+        } else {
+            let global = overrides::apply(&self.global_settings, &changes);
+            self.set_settings(global);
+        }
+    }
+
+    /// Drop the current game's overrides, back to the global settings.
+    fn clear_game_settings(&mut self) {
+        self.overrides.clear(self.current_game_id());
+        self.apply_settings();
+    }
+
+    /// Resolve the settings for the current game and push them to the
+    /// subsystems that cache them.
+    fn apply_settings(&mut self) {
+        let settings = self
+            .overrides
+            .resolve(&self.global_settings, self.current_game_id());
+        self.input.apply_settings(&settings);
+        if !settings.touch_controls {
+            self.touch_controls.clear(&mut self.input.keyboard);
+        }
+        if self.primary {
+            audio::set_volumes(&settings);
         }
+        self.settings = settings;
     }
 
-    fn tick(&mut self, now: f64) {
+    /// Advance and draw one frame. Returns false when the FPS cap skipped it.
+    fn tick(&mut self, now: f64) -> bool {
+        self.input.keyboard.release_pulses(now);
+        if self.refresh.frame(now) {
+            logging::info(&format!(
+                "display refresh ~{:.0} Hz, interpolation {}",
+                self.refresh.hz().unwrap_or_default(),
+                if self.refresh.wants_interpolation() {
+                    "useful"
+                } else {
+                    "not needed"
+                }
+            ));
+        }
+
+        if let Some(msg) = self.announcer.poll(now) {
+            if let Some(on_announce) = self.on_announce.clone() {
+                // Deferred like `notify_game_changed`.
+                spawn_local(async move { on_announce.emit(msg) });
+            }
+        }
+        if self.input.take_activity() {
+            self.idle.input(now);
+            self.attract.input(now);
+        }
+        if self.kiosk && self.attract.due(now) {
+            let weights: Vec<f32> = self
+                .games
+                .iter()
+                .map(|g| {
+                    let id = g.metadata().id;
+                    if self.unavailable.contains(id) {
+                        0.0
+                    } else {
+                        profiles::profile(id).attract_weight
+                    }
+                })
+                .collect();
+            if let Some(idx) = self.attract.pick(self.selected, &weights) {
+                self.select_game(idx);
+            }
+        }
+        let brightness =
+            self.idle
+                .brightness(now, self.settings.idle_dim_s, self.settings.idle_fade_s);
+        let low_power = self.settings.low_power.active(self.battery.is_low());
+        let max_hz = if brightness <= 0.0 {
+            Some(idle::SLEEP_HZ)
+        } else if low_power {
+            let cap = self.settings.fps_cap.max_hz(self.refresh.hz());
+            Some(cap.map_or(power::LOW_POWER_HZ, |hz| hz.min(power::LOW_POWER_HZ)))
+        } else {
+            self.settings.fps_cap.max_hz(self.refresh.hz())
+        };
+        if !self.limiter.due(now, max_hz) {
+            return false;
+        }
+        // Uncapped, the budget is the display's own refresh interval.
+        let budget_ms = 1000.0 / max_hz.or(self.refresh.hz()).unwrap_or(60.0);
+        let glow_scale = if self.settings.adaptive_glow {
+            self.adaptive_glow.frame(now, budget_ms)
+        } else {
+            // Start from scratch when it comes back on.
+            self.adaptive_glow = AdaptiveGlow::default();
+            1.0
+        };
+
+        if self.settings.warm_up {
+            self.warm_next(now);
+        }
+
+        if let Some(idx) = self.menu.poll(self.games.len(), now) {
+            self.show_menu_highlight(idx, now);
+        }
+
+        if let Some(bench) = self.bench.as_mut() {
+            self.draw_cmds.clear();
+            bench.frame(now, &mut self.draw_cmds);
+            if self.cull {
+                let culled = display_list::cull_offscreen(&mut self.draw_cmds, &self.screen);
+                bench.record_culled(culled);
+            }
+            return true;
+        }
+
+        if self.self_test.running(now, self.settings.self_test_s) {
+            self.draw_cmds.clear();
+            self.self_test.draw(
+                now,
+                self.settings.self_test_s,
+                &self.screen,
+                &mut self.draw_cmds,
+            );
+            return true;
+        }
+
         if self.last_time == 0.0 {
             self.last_time = now;
         }
         let dt = ((now - self.last_time) / 1000.0) as f32;
         self.last_time = now;
-        self.accumulator += dt.min(0.25); // cap to avoid spiral of death
+        if dt > 0.25 {
+            logging::debug(&format!("frame dt {:.3}s clamped to 0.25s", dt));
+        }
+        // Vsync-locked stepping runs one step per on-time frame; a late
+        // frame (or a capped rate) falls back to the accumulator to catch up.
+        let locked = self.settings.stepping.locked(self.refresh.hz()) && dt < TIMESTEP * 1.5;
+        if locked {
+            self.accumulator = TIMESTEP;
+        } else {
+            self.accumulator += dt.min(0.25); // cap to avoid spiral of death
+        }
+        self.input.keyboard.advance(dt.min(0.25));
+        if let Some(entry) = self.initials.as_mut() {
+            entry.cycle(&self.input.keyboard);
+        }
 
+        let orientation = self.orientation();
         let audio = WebAudio;
-        let mut did_update = false;
+        let composite = self.input.composite();
+        let input =
+            RotatedInput::new(&composite, orientation).with_invert_y(self.settings.invert_y);
+        if self.continue_prompt.expired(now) {
+            self.reset_current();
+        }
+        let paused = self.pause.frozen(now, self.settings.resume_countdown_s);
+        let frozen = paused || self.continue_prompt.is_active() || self.initials.is_some();
+        if self.pause.is_long(now) && !self.sleeping {
+            if let Some(on_sleep) = self.on_sleep.clone() {
+                self.sleeping = true;
+                logging::debug("long pause; frame loop stopped");
+                // Deferred: the loop can't be dropped from inside its own frame.
+                spawn_local(async move { on_sleep.emit(true) });
+            }
+        }
+        let mut steps = 0;
+        if let Some(Spectate::Watch(spectator)) = self.spectate.as_mut() {
+            // Spectators step only on the player's broadcast steps.
+            let events = spectator.poll();
+            self.accumulator = 0.0;
+            for event in events {
+                match event {
+                    spectate::Event::Start { game, seed } => {
+                        self.rng = Xorshift64::new(seed);
+                        if game == self.current_game_id() {
+                            self.reset_selected();
+                        } else {
+                            self.select_game_by_id(&game);
+                        }
+                    }
+                    spectate::Event::Step(snapshot) => {
+                        let mut ctx = GameCtx {
+                            input: &snapshot,
+                            audio: &audio,
+                            rng: &mut self.rng,
+                            screen: self.screen,
+                            now_s: now / 1000.0,
+                        };
+                        if let Some(game) = self.games.get_mut(self.selected) {
+                            game.update(&mut ctx, TIMESTEP);
+                        }
+                        steps += 1;
+                    }
+                }
+            }
+        }
         while self.accumulator >= TIMESTEP {
             let mut ctx = GameCtx {
-                input: &self.input,
+                input: &input,
                 audio: &audio,
                 rng: &mut self.rng,
                 screen: self.screen,
                 now_s: now / 1000.0,
             };
-            if let Some(game) = self.games.get_mut(self.selected) {
+            if let Some(game) = self.games.get_mut(self.selected).filter(|_| !frozen) {
+                if let Some(Spectate::Play(broadcaster)) = self.spectate.as_mut() {
+                    broadcaster.send(spectate::Event::Step(Snapshot::capture(&input)));
+                }
                 game.update(&mut ctx, TIMESTEP);
+                if self.played_since_reset {
+                    self.stats.add_time(game.metadata().id, TIMESTEP);
+                }
             }
             self.accumulator -= TIMESTEP;
-            did_update = true;
+            steps += 1;
+        }
+        let did_update = steps > 0;
+        if let Some(drift) = self.drift.as_mut() {
+            drift.frame(now, dt as f64, steps, self.refresh.hz());
         }
 
         self.draw_cmds.clear();
+        debug_draw::clear();
         let mut ctx = GameCtx {
-            input: &self.input,
+            input: &input,
             audio: &audio,
             rng: &mut self.rng,
             screen: self.screen,
@@ -181,6 +609,242 @@ impl GameState {
         };
         if let Some(game) = self.games.get_mut(self.selected) {
             game.render(&mut ctx, &mut self.draw_cmds);
+        } else if self.games.is_empty() {
+            // Say so rather than leave a blank screen on a misconfigured build.
+            overlay::text_centered(
+                &mut self.draw_cmds,
+                &self.screen,
+                Vec2::ZERO,
+                "NO GAMES AVAILABLE",
+                24.0,
+                Rgba(1.0, 0.4, 0.4, 1.0),
+            );
+        }
+        let game_drew = !self.draw_cmds.is_empty();
+        if self.splash.holding(self.settings.boot_banner) {
+            self.draw_cmds.clear();
+        }
+        let budget = (self.settings.primitive_budget_k * 1000.0) as usize;
+        let over_budget = display_list::limit_primitives(&mut self.draw_cmds, budget);
+        if over_budget && !self.over_budget {
+            logging::warn(&format!(
+                "{} exceeded the render budget of {} primitives; the rest of the frame was dropped",
+                self.current_game_id(),
+                budget
+            ));
+        }
+        self.over_budget = over_budget;
+        if self.settings.beam_vectors > 0.0 {
+            self.beam_frame += 1;
+            let vectors = self.settings.beam_vectors as usize;
+            display_list::beam_budget(&mut self.draw_cmds, vectors, self.beam_frame);
+        }
+        if let Some(histogram) = self.cmd_histogram.as_mut() {
+            *histogram = CmdHistogram::of(&self.draw_cmds);
+        }
+        self.reveal
+            .apply(now, self.settings.reveal_s, &mut self.draw_cmds);
+        debug_draw::drain_into(&mut self.draw_cmds);
+        if let Some(crosshair) = self.profile.crosshair {
+            let at = if self.input.pointer.is_locked() {
+                Some(Vec2::ZERO)
+            } else {
+                input.pointer().map(|p| p.pos)
+            };
+            if let Some(at) = at {
+                crosshair.draw(at, &self.screen, &mut self.draw_cmds);
+            }
+        }
+        display_list::apply_clear_policy(&mut self.draw_cmds, self.profile.clear_policy);
+        orientation::orient_cmds(&mut self.draw_cmds, orientation);
+        let scale = self.playfield_scale();
+        if scale != 1.0 {
+            display_list::map_points(&mut self.draw_cmds, |p| p * scale);
+        }
+        if self.settings.relative_text {
+            let factor = scaling::text_scale(&self.screen, scale);
+            display_list::scale_text(&mut self.draw_cmds, factor);
+        }
+        self.view.apply(now, &mut self.draw_cmds);
+        self.crossfade.blend(now, &mut self.draw_cmds);
+        let bars = match self.settings.letterbox {
+            LetterboxBars::GameClear => None,
+            LetterboxBars::Fixed => Some(self.settings.letterbox_color),
+            LetterboxBars::Darkened => Some(self.clear_color().map(|c| c * 0.4)),
+        };
+        if let Some([r, g, b]) = bars {
+            scaling::letterbox_bars(&self.screen, scale, Rgba(r, g, b, 1.0), &mut self.draw_cmds);
+        }
+        if self.settings.credits {
+            self.credits.draw(&self.screen, &mut self.draw_cmds);
+        }
+        self.continue_prompt
+            .draw(now, &self.screen, &mut self.draw_cmds);
+        self.pause.draw(now, &self.screen, &mut self.draw_cmds);
+        if let Some(entry) = &self.initials {
+            entry.draw(now, &self.screen, &mut self.draw_cmds);
+        } else {
+            self.draw_high_score();
+        }
+        self.toast.draw(now, &self.screen, &mut self.draw_cmds);
+        if let Some(game) = self.menu.highlighted().and_then(|idx| self.games.get(idx)) {
+            // Marching ants around the browse toast mark it as a live choice.
+            let label = menu_label(game.metadata().name);
+            let pad = Vec2::new(0.03, 0.02);
+            let half_w = overlay::text_width(&self.screen, &label, toast::SIZE_PX) * 0.5;
+            let height = toast::SIZE_PX * overlay::ndc_per_px(&self.screen);
+            let (min, max) = (
+                Vec2::new(-half_w, toast::Y) - pad,
+                Vec2::new(half_w, toast::Y + height) + pad,
+            );
+            let color = Rgba(1.0, 1.0, 0.6, 0.8);
+            overlay::marching_ants(
+                &mut self.draw_cmds,
+                &self.screen,
+                min,
+                max,
+                now / 1000.0,
+                color,
+            );
+        }
+        if self.show_wireframe {
+            overlay::ndc_wireframe(&mut self.draw_cmds, &self.screen);
+        }
+        if self.settings.touch_controls {
+            self.touch_controls.draw(&mut self.draw_cmds);
+        }
+        if self.show_input {
+            input_viz::draw(&input, &self.screen, &mut self.draw_cmds);
+        }
+        if let Some(histogram) = &self.cmd_histogram {
+            histogram.draw(&self.screen, &mut self.draw_cmds);
+        }
+        if let Some(drift) = &self.drift {
+            drift.draw(&self.screen, &mut self.draw_cmds);
+        }
+        if over_budget {
+            overlay::text(
+                &mut self.draw_cmds,
+                Vec2::new(-0.95, -0.97),
+                "RENDER BUDGET EXCEEDED",
+                10.0,
+                Rgba(1.0, 0.3, 0.3, 1.0),
+            );
+        }
+        self.splash.draw(
+            now,
+            game_drew,
+            self.settings.boot_banner,
+            &self.screen,
+            &mut self.draw_cmds,
+        );
+
+        let cycle_speed = self.settings.palette_cycle_speed;
+        if cycle_speed > 0.0 && self.profile.palette_cycle {
+            let angle = (now / 1000.0 * cycle_speed as f64).fract() as f32 * std::f32::consts::TAU;
+            display_list::for_each_color(&mut self.draw_cmds, |c| {
+                *c = color::rotate_hue(*c, angle);
+            });
+        }
+
+        if let Some(hue) = self.settings.phosphor.hue() {
+            display_list::for_each_color(&mut self.draw_cmds, |c| *c = color::phosphor(*c, hue));
+        }
+
+        if brightness < 1.0 {
+            display_list::for_each_color(&mut self.draw_cmds, |c| {
+                *c = Rgba(c.0 * brightness, c.1 * brightness, c.2 * brightness, c.3);
+            });
+        }
+
+        if self.settings.linear_color {
+            display_list::for_each_color(&mut self.draw_cmds, |c| *c = color::linear_to_srgb(*c));
+            display_list::for_each_clear(&mut self.draw_cmds, |c| *c = color::linear_to_srgb(*c));
+        }
+
+        let [r, g, b] = self.settings.background;
+        display_list::fill_transparent_clears(&mut self.draw_cmds, Rgba(r, g, b, 1.0));
+        if self.settings.border {
+            let [r, g, b] = self.settings.border_color;
+            let stroke = Stroke {
+                color: Rgba(r, g, b, 1.0),
+                width_px: 2.0,
+                glow: 1.0,
+            };
+            let start = self.draw_cmds.len();
+            overlay::rounded_rect(
+                &mut self.draw_cmds,
+                Vec2::splat(-0.98),
+                Vec2::splat(0.98),
+                0.05,
+                stroke,
+            );
+            if self.settings.border_glow_color != self.settings.border_color {
+                let [r, g, b] = self.settings.border_glow_color;
+                display_list::glow_in_color(&mut self.draw_cmds, start, Rgba(r, g, b, 1.0));
+            }
+        }
+
+        if self.kiosk {
+            kiosk::apply_jitter(
+                now / 1000.0,
+                self.settings.jitter_px,
+                self.settings.jitter_period_s,
+                &self.screen,
+                &mut self.draw_cmds,
+            );
+        }
+
+        if let Some(tuning) = self.tuning {
+            tuning.apply(&mut self.draw_cmds);
+        }
+        if low_power {
+            display_list::for_each_stroke(&mut self.draw_cmds, |stroke| stroke.glow = 0.0);
+            overlay::text(
+                &mut self.draw_cmds,
+                Vec2::new(-0.95, -0.95),
+                "ECO",
+                10.0,
+                Rgba(0.4, 0.8, 0.4, 0.6),
+            );
+        } else if self.settings.crisp_lines {
+            display_list::for_each_stroke(&mut self.draw_cmds, |stroke| stroke.glow = 0.0);
+        } else if self.settings.adaptive_glow && self.adaptive_glow.is_reducing() {
+            display_list::for_each_stroke(&mut self.draw_cmds, |stroke| {
+                stroke.glow *= glow_scale;
+            });
+            let label = format!("GLOW {:.0}%", glow_scale * 100.0);
+            let color = Rgba(0.8, 0.6, 0.2, 0.7);
+            overlay::text_directed(
+                &mut self.draw_cmds,
+                &self.screen,
+                Vec2::new(0.95, 0.92),
+                &label,
+                10.0,
+                color,
+                TextDirection::Rtl,
+            );
+        }
+
+        display_list::quantize(&mut self.draw_cmds, &self.screen, self.settings.quantize_px);
+        self.text_snap.apply(&mut self.draw_cmds, &self.screen);
+
+        if self.cull {
+            display_list::cull_offscreen(&mut self.draw_cmds, &self.screen);
+        }
+
+        if self.settings.beam_core && !self.settings.crisp_lines && !low_power {
+            display_list::beam_core(&mut self.draw_cmds, self.settings.beam_core_ratio);
+        }
+        if self.settings.glow_layers {
+            display_list::layer_glow(&mut self.draw_cmds);
+        }
+
+        if let Some(json) = self.frame_json.as_mut() {
+            *json = export::to_json(&self.draw_cmds);
+        }
+        if let Some(bytes) = self.frame_binary.as_mut() {
+            *bytes = export::encode_draw_cmds(&self.draw_cmds);
         }
 
         // Only clear input state if we actually ran an update
@@ -188,36 +852,467 @@ impl GameState {
         if did_update {
             self.input.end_frame();
         }
+        true
+    }
+
+    /// Feed a touch pointer event to the swipe recognizer.
+    fn handle_swipe(&mut self, e: &PointerEvent) {
+        let pos = Vec2::new(e.offset_x() as f32, e.offset_y() as f32);
+        let now = e.time_stamp();
+        match e.type_().as_str() {
+            "pointerdown" => self.swipe.begin(pos, now),
+            "pointerup" => {
+                let threshold = self.settings.swipe_threshold_px;
+                if let Some(key) = self.swipe.end(pos, now, threshold) {
+                    self.input.keyboard.pulse_key(key, now + SWIPE_PRESS_MS);
+                    self.played_since_reset = true;
+                }
+            }
+            "pointercancel" => self.swipe.cancel(),
+            _ => {}
+        }
+    }
+
+    /// Handle platform hotkeys. Returns true if the key was consumed.
+    fn hotkey(&mut self, code: &str) -> bool {
+        if self.self_test.skip() {
+            return true;
+        }
+        if self.settings.boot_banner && self.splash.skip(self.last_time) {
+            return true;
+        }
+        if let Some(entry) = self.initials.as_mut() {
+            if let Some(initials) = entry.key(code) {
+                let id = self.current_game_id();
+                self.stats.record_best(id, entry.score, &initials);
+                self.initials = None;
+            }
+            // Up/down go on to the keyboard state, where holding repeats.
+            return !InitialsEntry::is_cycle_key(code);
+        }
+        if self.continue_prompt.is_active() {
+            return self.continue_hotkey(code);
+        }
+        match code {
+            "F2" => self.show_wireframe = !self.show_wireframe,
+            "F3" => self.show_input = !self.show_input,
+            "F4" => self.cmd_histogram = self.cmd_histogram.is_none().then(CmdHistogram::default),
+            "KeyP" => self.toggle_pause(),
+            "KeyH" => self.set_chrome(!self.settings.show_chrome),
+            "F8" if self.tuning.is_some() => self.toggle_tuning(),
+            "F10" => {
+                let on = debug_draw::toggle();
+                self.toast.show(
+                    if on { "HITBOXES ON" } else { "HITBOXES OFF" },
+                    self.last_time,
+                );
+            }
+            "F9" => self.frame_dump.arm(self.settings.frame_dump_count as u32),
+            // Checkpoints need game state snapshots, which no game exposes yet.
+            "F6" | "F7" => self.toast.show("CHECKPOINTS UNAVAILABLE", self.last_time),
+            "Digit5" if self.settings.credits => self.credits.insert_coin(),
+            "PageUp" | "PageDown" => {
+                let dir = GameMenu::direction(code).unwrap_or_default();
+                let now = self.last_time;
+                let idx = self.menu.press(dir, self.selected, self.games.len(), now);
+                self.show_menu_highlight(idx, now);
+            }
+            // Starting from the menu costs a credit; without one, Enter is swallowed.
+            "Enter" if self.settings.credits => {
+                if self.credits.try_start() {
+                    return false;
+                }
+                self.toast.show("INSERT COIN", self.last_time);
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Keys while the continue prompt is up: Enter continues (spending a
+    /// credit when credits are on) and inserting a coin continues at once.
+    fn continue_hotkey(&mut self, code: &str) -> bool {
+        let now = self.last_time;
+        match code {
+            "Digit5" if self.settings.credits => {
+                self.credits.insert_coin();
+                self.credits.spend();
+                self.continue_prompt.accept();
+            }
+            "Enter" if self.settings.credits && !self.credits.spend() => {
+                self.toast.show("INSERT COIN", now);
+            }
+            "Enter" => self.continue_prompt.accept(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Take the final score of a run; a new best asks for initials.
+    fn report_score(&mut self, score: u64) {
+        let best = self.stats.get(self.current_game_id()).best_score;
+        if score > best.unwrap_or(0) {
+            self.initials = Some(InitialsEntry::new(score));
+        }
+    }
+
+    /// Draw "HI: ABC 12345" for the current game once it has a best score.
+    fn draw_high_score(&mut self) {
+        let stats = self.stats.get(self.current_game_id());
+        if let Some(best) = stats.best_score {
+            let label = format!("HI: {} {}", stats.best_initials, best);
+            let color = Rgba(0.7, 0.7, 0.7, 0.8);
+            overlay::text_centered(
+                &mut self.draw_cmds,
+                &self.screen,
+                Vec2::new(0.0, 0.92),
+                &label,
+                12.0,
+                color,
+            );
+        }
+    }
+
+    /// Freeze the game and count down to a reset unless the player continues.
+    fn offer_continue(&mut self) {
+        self.continue_prompt
+            .offer(self.last_time, self.settings.continue_s);
+    }
+
+    /// User rotation and mirroring combined with the game's preferred turns.
+    fn orientation(&self) -> Orientation {
+        Orientation {
+            turns: self.settings.rotation.quarter_turns() + self.profile.quarter_turns,
+            flip_x: self.settings.flip_x,
+            flip_y: self.settings.flip_y,
+        }
+    }
+
+    /// The color this frame clears to: its first `Clear`, with the platform
+    /// background standing in for a transparent one.
+    fn clear_color(&self) -> [f32; 3] {
+        self.draw_cmds
+            .iter()
+            .find_map(|cmd| match cmd {
+                DrawCmd::Clear { color } if color.3 > 0.0 => Some([color.0, color.1, color.2]),
+                DrawCmd::Clear { .. } => Some(self.settings.background),
+                _ => None,
+            })
+            .unwrap_or(self.settings.background)
+    }
+
+    /// Factor the scaling mode applies to game coordinates.
+    fn playfield_scale(&self) -> f32 {
+        scaling::scale(self.settings.scale_mode, &self.screen)
+    }
+
+    /// Restart frame timing after the loop was stopped, so the gap is not
+    /// replayed as a burst of fixed steps.
+    fn resume(&mut self) {
+        self.last_time = 0.0;
+        self.accumulator = 0.0;
+        self.pause.resume();
+        self.sleeping = false;
+    }
+
+    /// Handle platform hotkey releases. Returns true if the key was consumed.
+    fn hotkey_up(&mut self, code: &str) -> bool {
+        let Some(dir) = GameMenu::direction(code) else {
+            return false;
+        };
+        if let Some(idx) = self.menu.release(dir) {
+            self.select_game(idx);
+        }
+        true
+    }
+
+    fn show_menu_highlight(&mut self, idx: usize, now: f64) {
+        if let Some(game) = self.games.get(idx) {
+            self.toast.show(menu_label(game.metadata().name), now);
+        }
     }
 
     fn select_game(&mut self, idx: usize) {
         if idx < self.games.len() && idx != self.selected {
+            let meta = self.games[idx].metadata();
+            if self.unavailable.contains(meta.id) {
+                self.toast.show(
+                    format!("{} UNAVAILABLE", meta.name.to_uppercase()),
+                    self.last_time,
+                );
+                self.notify_game_changed();
+                return;
+            }
+            let outgoing = self.game_frame();
+            self.crossfade.start(outgoing, self.last_time);
+            let previous = self.selected;
             self.selected = idx;
-            let audio = WebAudio;
-            let mut ctx = GameCtx {
-                input: &self.input,
-                audio: &audio,
-                rng: &mut self.rng,
-                screen: self.screen,
-                now_s: 0.0,
-            };
-            if let Some(game) = self.games.get_mut(self.selected) {
-                game.reset(&mut ctx);
+            logging::info(&format!("switched to {}", meta.name));
+            self.announcer.announce(meta.name);
+            self.profile = profiles::profile(meta.id);
+            self.apply_settings();
+            if !self.reset_selected() {
+                // Back to the game that was running; its state is untouched.
+                self.selected = previous;
+                self.profile = profiles::profile(self.current_game_id());
+                self.apply_settings();
+                self.crossfade = Crossfade::default();
+                self.toast.show(
+                    format!("{} FAILED TO START", meta.name.to_uppercase()),
+                    self.last_time,
+                );
+                self.notify_game_changed();
+                return;
+            }
+            self.log_params();
+            self.reveal.start(self.last_time);
+            self.device_notice();
+            if self.primary {
+                self.stats.record_play(meta.id);
+                storage::save(LAST_GAME_KEY, &meta.id);
+            }
+            self.notify_game_changed();
+            self.played_since_reset = false;
+            self.credits.end_run();
+        }
+    }
+
+    /// Render the current game alone, oriented and scaled as `tick` would.
+    fn game_frame(&mut self) -> Vec<DrawCmd> {
+        let mut frame = Vec::new();
+        let audio = WebAudio;
+        let input = self.input.composite();
+        let mut ctx = GameCtx {
+            input: &input,
+            audio: &audio,
+            rng: &mut self.rng,
+            screen: self.screen,
+            now_s: self.last_time / 1000.0,
+        };
+        if let Some(game) = self.games.get_mut(self.selected) {
+            game.render(&mut ctx, &mut frame);
+        }
+        orientation::orient_cmds(&mut frame, self.orientation());
+        let scale = self.playfield_scale();
+        if scale != 1.0 {
+            display_list::map_points(&mut frame, |p| p * scale);
+        }
+        frame
+    }
+
+    /// Switch to the game whose `GameMeta::id` is `id`. Returns false if no
+    /// game has that id.
+    fn select_game_by_id(&mut self, id: &str) -> bool {
+        match self.games.iter().position(|g| g.metadata().id == id) {
+            Some(idx) => {
+                self.select_game(idx);
+                true
+            }
+            None => {
+                logging::warn(&format!("no game with id {:?}", id));
+                false
+            }
+        }
+    }
+
+    /// Stable id of the running game (empty if there are no games).
+    fn current_game_id(&self) -> &'static str {
+        self.games
+            .get(self.selected)
+            .map_or("", |g| g.metadata().id)
+    }
+
+    /// Show or hide the HUD and panel, remembering the choice.
+    fn set_chrome(&mut self, show: bool) {
+        let mut settings = self.global_settings.clone();
+        settings.show_chrome = show;
+        self.set_settings(settings);
+        if let Some(on_chrome_changed) = self.on_chrome_changed.clone() {
+            // Deferred like `notify_game_changed`.
+            spawn_local(async move { on_chrome_changed.emit(show) });
+        }
+    }
+
+    /// Toast a notice when the current game needs a device that's missing.
+    fn device_notice(&mut self) {
+        let touch_controls_off = !self.settings.touch_controls;
+        if let Some(msg) = devices::notice(self.profile.needs, touch_controls_off) {
+            self.toast.show(msg, self.last_time);
+        }
+    }
+
+    /// Pause or unpause, restarting the frame loop if a long pause stopped it.
+    fn toggle_pause(&mut self) {
+        self.pause.toggle();
+        if std::mem::take(&mut self.sleeping) {
+            if let Some(on_sleep) = self.on_sleep.clone() {
+                // Deferred like `notify_game_changed`.
+                spawn_local(async move { on_sleep.emit(false) });
             }
         }
     }
 
+    /// Show or hide the developer tuning panel.
+    fn toggle_tuning(&mut self) {
+        self.show_tuning = !self.show_tuning;
+        if let Some(on_tuning_toggled) = self.on_tuning_toggled.clone() {
+            let show = self.show_tuning;
+            // Deferred like `notify_game_changed`.
+            spawn_local(async move { on_tuning_toggled.emit(show) });
+        }
+    }
+
+    /// Let the UI know which game is selected now.
+    fn notify_game_changed(&self) {
+        if let Some(on_game_changed) = self.on_game_changed.clone() {
+            let id = self.current_game_id();
+            // Deferred: the UI reads this state while re-rendering.
+            spawn_local(async move { on_game_changed.emit(id) });
+        }
+    }
+
+    /// Swap the running game for a fresh instance from the registry.
+    fn reinstantiate_current(&mut self) -> bool {
+        let id = self.current_game_id();
+        let Some(fresh) = scripted::registry()
+            .into_iter()
+            .find(|g| g.metadata().id == id)
+        else {
+            logging::warn(&format!("{:?} is no longer registered", id));
+            return false;
+        };
+        self.games[self.selected] = fresh;
+        self.reset_current();
+        logging::info(&format!("reloaded {}", id));
+        true
+    }
+
+    /// Add a game to the list, replacing any game with the same id.
+    fn register_game(&mut self, game: Box<dyn Game>) {
+        let id = game.metadata().id;
+        self.unavailable.remove(id);
+        match self.games.iter().position(|g| g.metadata().id == id) {
+            Some(idx) => {
+                self.games[idx] = game;
+                if idx == self.selected {
+                    self.reset_current();
+                }
+            }
+            None => self.games.push(game),
+        }
+        self.notify_game_changed();
+    }
+
+    /// Rebuild every game from the registry, keeping the selection by id.
+    fn rebuild_registry(&mut self) {
+        let id = self.current_game_id();
+        self.games = scripted::registry();
+        self.selected = self
+            .games
+            .iter()
+            .position(|g| g.metadata().id == id)
+            .unwrap_or(0);
+        self.profile = profiles::profile(self.current_game_id());
+        self.apply_settings();
+        self.reset_current();
+        self.notify_game_changed();
+        logging::info(&format!("registry rebuilt: {} games", self.games.len()));
+    }
+
     fn reset_current(&mut self) {
+        if !self.reset_selected() {
+            let fallback = (0..self.games.len())
+                .find(|&i| !self.unavailable.contains(self.games[i].metadata().id));
+            if let Some(idx) = fallback {
+                self.select_game(idx);
+            }
+            return;
+        }
+        if self.primary {
+            self.stats.record_play(self.current_game_id());
+        }
+        self.log_params();
+        self.played_since_reset = false;
+        self.credits.end_run();
+        self.continue_prompt.accept();
+    }
+
+    /// Reset the selected game, containing a panic. A game that panics is
+    /// marked unavailable and false is returned.
+    fn reset_selected(&mut self) -> bool {
+        let id = self.current_game_id();
+        if let Some(Spectate::Play(broadcaster)) = self.spectate.as_mut() {
+            // A fresh seed per run, shared so spectators can replay it.
+            let seed = (js_sys::Math::random() * 9_007_199_254_740_992.0) as u64 | 1;
+            self.rng = Xorshift64::new(seed);
+            broadcaster.send(spectate::Event::Start {
+                game: id.to_string(),
+                seed,
+            });
+        }
         let audio = WebAudio;
+        let input = self.input.composite();
         let mut ctx = GameCtx {
-            input: &self.input,
+            input: &input,
             audio: &audio,
             rng: &mut self.rng,
             screen: self.screen,
             now_s: 0.0,
         };
-        if let Some(game) = self.games.get_mut(self.selected) {
-            game.reset(&mut ctx);
+        let Some(game) = self.games.get_mut(self.selected) else {
+            return true;
+        };
+        if crash_guard::guarded(id, || game.reset(&mut ctx)).is_some() {
+            return true;
+        }
+        self.unavailable.insert(id);
+        false
+    }
+
+    /// Warm up the next game in line, if one is due (see `warmup`).
+    fn warm_next(&mut self, now: f64) {
+        let Some(idx) = self.warm_up.next_game(now, self.games.len(), self.selected) else {
+            return;
+        };
+        let id = self.games[idx].metadata().id;
+        if self.unavailable.contains(id) {
+            return;
+        }
+        let game = self.games[idx].as_mut();
+        let screen = self.screen;
+        if crash_guard::guarded(id, || {
+            headless::run_headless(game, screen, warmup::SEED, warmup::TICKS)
+        })
+        .is_none()
+        {
+            self.unavailable.insert(id);
+            self.notify_game_changed();
+        }
+    }
+
+    /// Drop state kept in device pixels when the pixel ratio changes
+    /// (the window moved to another monitor).
+    fn check_dpr(&mut self, dpr: f32) {
+        let generation = dpr::generation();
+        let first_frame = self.screen.width_px == 0;
+        if generation == self.dpr_generation && (first_frame || dpr == self.screen.dpi_scale) {
+            return;
+        }
+        self.dpr_generation = generation;
+        logging::info(&format!("pixel ratio {} -> {}", self.screen.dpi_scale, dpr));
+        self.touch_controls.clear(&mut self.input.keyboard);
+        self.swipe.cancel();
+    }
+
+    fn is_unavailable(&self, id: &str) -> bool {
+        self.unavailable.contains(id)
+    }
+
+    /// `GameCtx` cannot carry the URL parameters yet, so a reset only logs
+    /// what the game would have been given.
+    fn log_params(&self) {
+        if !self.params.is_empty() {
+            logging::debug(&format!("game params: {:?}", self.params));
         }
     }
 
@@ -227,15 +1322,21 @@ impl GameState {
 }
 
 thread_local! {
-    static GAME_STATE: RefCell<GameState> = RefCell::new(GameState::new());
-    static RENDERER: RefCell<Option<WgpuRenderer>> = const { RefCell::new(None) };
+    /// State and renderer of the primary instance, driven by the Yew `App`.
+    static GAME_STATE: Rc<RefCell<GameState>> = Rc::new(RefCell::new(GameState::new()));
+    static RENDERER: Rc<RefCell<Option<WgpuRenderer>>> = Rc::default();
 }
 
 #[function_component(App)]
 fn app() -> Html {
     let canvas_ref = use_node_ref();
-    let selected = use_state(|| 0usize);
+    let selected = use_state(|| GAME_STATE.with(|state| state.borrow().current_game_id()));
     let renderer_ready = use_state(|| false);
+    let graphics_lost = use_state(|| false);
+    let pointer_locked = use_state(|| false);
+    let show_chrome = use_state(|| GAME_STATE.with(|state| state.borrow().settings.show_chrome));
+    let announcement = use_state(String::new);
+    let show_tuning = use_state(|| false);
 
     // Get game metadata for the dropdown
     let game_meta: Vec<GameMeta> = GAME_STATE.with(|state| state.borrow().game_metadata());
@@ -244,90 +1345,234 @@ fn app() -> Html {
     {
         let canvas_ref = canvas_ref.clone();
         let renderer_ready = renderer_ready.clone();
+        let graphics_lost = graphics_lost.clone();
+        let pointer_locked = pointer_locked.clone();
+        let selected = selected.clone();
+        let show_chrome = show_chrome.clone();
+        let announcement = announcement.clone();
+        let show_tuning = show_tuning.clone();
         use_effect_with((), move |_| {
             let window = web_sys::window().expect("no window");
             let document = window.document().expect("no document");
 
-            // Setup keyboard listeners
-            let keydown = Closure::<dyn FnMut(KeyboardEvent)>::new(move |e: KeyboardEvent| {
-                if let Some(key) = WebInput::map_code(&e.code()) {
-                    GAME_STATE.with(|state| {
-                        state.borrow_mut().input.set_key(key, true);
-                    });
-                    e.prevent_default();
-                }
-            });
-            let keyup = Closure::<dyn FnMut(KeyboardEvent)>::new(move |e: KeyboardEvent| {
-                if let Some(key) = WebInput::map_code(&e.code()) {
-                    GAME_STATE.with(|state| {
-                        state.borrow_mut().input.set_key(key, false);
-                    });
-                }
-            });
-            document
-                .add_event_listener_with_callback("keydown", keydown.as_ref().unchecked_ref())
-                .unwrap();
+            let primary = Instance::primary();
+            primary.state.borrow_mut().on_game_changed =
+                Some(Callback::from(move |id: &'static str| selected.set(id)));
+            primary.state.borrow_mut().on_chrome_changed =
+                Some(Callback::from(move |show: bool| show_chrome.set(show)));
+            primary.state.borrow_mut().on_announce =
+                Some(Callback::from(move |msg: String| announcement.set(msg)));
+            primary.state.borrow_mut().on_tuning_toggled =
+                Some(Callback::from(move |show: bool| show_tuning.set(show)));
+            setup_key_listeners(&document, &primary.state, false).forget();
+            dpr::watch();
+
+            let on_graphics_lost = Callback::from(move |lost: bool| graphics_lost.set(lost));
+            let canvas = canvas_ref.cast::<HtmlCanvasElement>();
+            if let Some(canvas) = &canvas {
+                let on_lock = Callback::from(move |locked: bool| pointer_locked.set(locked));
+                setup_pointer_listeners(canvas, &primary.state, on_lock).forget();
+                setup_context_loss_listeners(canvas, &primary.renderer, on_graphics_lost).forget();
+            }
+
+            let frame_loop: Rc<RefCell<Option<FrameLoop>>> = Rc::default();
+
+            // Stop the loop during a long pause and restart it on unpause
+            let on_sleep = {
+                let frame_loop = frame_loop.clone();
+                let canvas = canvas.clone();
+                let primary = primary.clone();
+                let document = document.clone();
+                Callback::from(move |sleep: bool| {
+                    let mut slot = frame_loop.borrow_mut();
+                    if sleep {
+                        slot.take();
+                    } else if slot.is_none()
+                        && !document.hidden()
+                        && primary.renderer.borrow().is_some()
+                    {
+                        if let Some(canvas) = &canvas {
+                            primary.state.borrow_mut().resume();
+                            *slot = Some(start_animation_loop(canvas.clone(), primary.clone()));
+                        }
+                    }
+                })
+            };
+            primary.state.borrow_mut().on_sleep = Some(on_sleep);
+
+            // Stop the loop while the page is hidden and restart it on return
+            let on_visibility = {
+                let frame_loop = frame_loop.clone();
+                let canvas = canvas.clone();
+                let primary = primary.clone();
+                let document = document.clone();
+                Closure::<dyn FnMut()>::new(move || {
+                    audio::set_suspended(document.hidden());
+                    let mut slot = frame_loop.borrow_mut();
+                    if document.hidden() {
+                        if slot.take().is_some() {
+                            logging::debug("page hidden; frame loop stopped");
+                        }
+                    } else if slot.is_none() && primary.renderer.borrow().is_some() {
+                        if let Some(canvas) = &canvas {
+                            primary.state.borrow_mut().resume();
+                            *slot = Some(start_animation_loop(canvas.clone(), primary.clone()));
+                        }
+                    }
+                })
+            };
             document
-                .add_event_listener_with_callback("keyup", keyup.as_ref().unchecked_ref())
+                .add_event_listener_with_callback(
+                    "visibilitychange",
+                    on_visibility.as_ref().unchecked_ref(),
+                )
                 .unwrap();
-            keydown.forget();
-            keyup.forget();
 
             // Initialize wgpu renderer asynchronously
-            let canvas_ref_clone = canvas_ref.clone();
+            let loop_slot = frame_loop.clone();
             spawn_local(async move {
-                if let Some(canvas) = canvas_ref_clone.cast::<HtmlCanvasElement>() {
-                    let window = web_sys::window().expect("no window");
-                    let dpr = window.device_pixel_ratio();
-                    let rect = canvas.get_bounding_client_rect();
-                    let width = (rect.width() * dpr) as u32;
-                    let height = (rect.height() * dpr) as u32;
-
-                    // Set initial canvas size
-                    canvas.set_width(width);
-                    canvas.set_height(height);
-
-                    match WgpuRenderer::new_web(canvas.clone(), width, height).await {
-                        Ok(renderer) => {
-                            RENDERER.with(|r| {
-                                *r.borrow_mut() = Some(renderer);
-                            });
-                            renderer_ready.set(true);
-
-                            // Start animation loop after renderer is ready
-                            start_animation_loop(canvas_ref_clone);
-                        }
-                        Err(e) => {
-                            web_sys::console::error_1(
-                                &format!("Failed to create wgpu renderer: {:?}", e).into(),
-                            );
-                        }
+                if let Some(canvas) = canvas {
+                    if create_renderer(canvas.clone(), &primary.renderer).await {
+                        renderer_ready.set(true);
+
+                        // Start animation loop after renderer is ready
+                        *loop_slot.borrow_mut() = Some(start_animation_loop(canvas, primary));
                     }
+                } else {
+                    logging::warn("canvas element missing; renderer not started");
                 }
             });
 
-            || {}
+            move || {
+                if let Some(document) = web_sys::window().and_then(|w| w.document()) {
+                    let _ = document.remove_event_listener_with_callback(
+                        "visibilitychange",
+                        on_visibility.as_ref().unchecked_ref(),
+                    );
+                }
+                frame_loop.borrow_mut().take();
+                GAME_STATE.with(|state| state.borrow_mut().on_sleep = None);
+            }
         });
     }
 
-    let on_change = {
-        let selected = selected.clone();
-        Callback::from(move |e: Event| {
-            let target = e.target_dyn_into::<web_sys::HtmlSelectElement>().unwrap();
-            let idx = target.value().parse::<usize>().unwrap_or(0);
-            selected.set(idx);
+    let on_change = Callback::from(move |e: Event| {
+        let target = e.target_dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+        GAME_STATE.with(|state| {
+            state.borrow_mut().select_game_by_id(&target.value());
+        });
+    });
+
+    // Reset confirmation state
+    let confirm_reset = use_state(|| false);
+
+    let on_reset = {
+        let confirm_reset = confirm_reset.clone();
+        Callback::from(move |_| {
+            let in_progress = GAME_STATE.with(|state| state.borrow().played_since_reset);
+            if in_progress {
+                confirm_reset.set(true);
+            } else {
+                GAME_STATE.with(|state| {
+                    state.borrow_mut().reset_current();
+                });
+            }
+        })
+    };
+
+    let on_confirm_reset = {
+        let confirm_reset = confirm_reset.clone();
+        Callback::from(move |_| {
             GAME_STATE.with(|state| {
-                state.borrow_mut().select_game(idx);
+                state.borrow_mut().reset_current();
             });
+            confirm_reset.set(false);
         })
     };
 
-    let on_reset = Callback::from(move |_| {
-        GAME_STATE.with(|state| {
-            state.borrow_mut().reset_current();
-        });
+    let on_cancel_reset = {
+        let confirm_reset = confirm_reset.clone();
+        Callback::from(move |_| {
+            confirm_reset.set(false);
+        })
+    };
+
+    // Clip recording state
+    let recording = use_state(|| false);
+    let record_secs = use_state(|| 10u32);
+    let recorder = use_mut_ref(|| None::<recorder::Recording>);
+
+    let on_record = {
+        let canvas_ref = canvas_ref.clone();
+        let recording = recording.clone();
+        let record_secs = record_secs.clone();
+        let recorder = recorder.clone();
+        Callback::from(move |_| {
+            let Some(canvas) = canvas_ref.cast::<HtmlCanvasElement>() else {
+                return;
+            };
+            let done = {
+                let recording = recording.clone();
+                move || recording.set(false)
+            };
+            match recorder::Recording::start(&canvas, done) {
+                Ok(rec) => {
+                    *recorder.borrow_mut() = Some(rec);
+                    recording.set(true);
+                    // Stop automatically once the chosen duration has elapsed.
+                    let recorder = recorder.clone();
+                    Timeout::new(*record_secs * 1000, move || {
+                        if let Some(rec) = recorder.borrow_mut().take() {
+                            rec.stop();
+                        }
+                    })
+                    .forget();
+                }
+                Err(e) => logging::error(&format!("recording failed to start: {:?}", e)),
+            }
+        })
+    };
+
+    let on_svg = Callback::from(|_| {
+        if let Err(e) = svg::download() {
+            logging::error(&format!("SVG export failed: {:?}", e));
+        }
     });
 
+    let on_stop_record = {
+        let recorder = recorder.clone();
+        Callback::from(move |_| {
+            if let Some(rec) = recorder.borrow_mut().take() {
+                rec.stop();
+            }
+        })
+    };
+
+    let on_record_secs = {
+        let record_secs = record_secs.clone();
+        Callback::from(move |e: Event| {
+            let target = e.target_dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+            record_secs.set(target.value().parse::<u32>().unwrap_or(10));
+        })
+    };
+
+    // Stats dialog state
+    let show_stats = use_state(|| false);
+
+    let on_stats = {
+        let show_stats = show_stats.clone();
+        Callback::from(move |_| {
+            show_stats.set(true);
+        })
+    };
+
+    let on_close_stats = {
+        let show_stats = show_stats.clone();
+        Callback::from(move |_| {
+            show_stats.set(false);
+        })
+    };
+
     // About dialog state
     let show_about = use_state(|| false);
 
@@ -345,21 +1590,150 @@ fn app() -> Html {
         })
     };
 
+    // Settings dialog state
+    let show_settings = use_state(|| false);
+
+    let on_settings = {
+        let show_settings = show_settings.clone();
+        Callback::from(move |_| {
+            show_settings.set(true);
+        })
+    };
+
+    let on_close_settings = {
+        let show_settings = show_settings.clone();
+        Callback::from(move |_| {
+            show_settings.set(false);
+        })
+    };
+
+    let on_reveal = Callback::from(|_| {
+        GAME_STATE.with(|state| state.borrow_mut().set_chrome(true));
+    });
+    let (hud_class, panel_class) =
+        GAME_STATE.with(|state| state.borrow().settings.hud_corner.classes());
+    // `--theme` colors the chrome (see index.html); unset falls back to white.
+    let theme = GAME_STATE
+        .with(|state| state.borrow().settings.themed_chrome)
+        .then(|| profiles::profile(*selected).theme)
+        .flatten()
+        .map(|rgb| format!(" --theme: {};", color::to_hex(rgb)))
+        .unwrap_or_default();
+
+    // Only offer controls the selected game supports.
+    let caps = profiles::profile(*selected).capabilities;
+    // Control hints name the connected controller's buttons.
+    let pad_family = pad::connected();
+
     html! {
-        <div style="position: relative; width: 100%; height: 100%;">
-            <div class="hud">{ "VectorCade" }</div>
-            <div class="panel">
-                <select onchange={on_change}>
-                    { for game_meta.iter().enumerate().map(|(i, g)| html!{
-                        <option value={i.to_string()} selected={*selected == i}>
-                            { g.name }
-                        </option>
-                    })}
-                </select>
-                <button onclick={on_reset}>{ "Reset" }</button>
-                <button onclick={on_about}>{ "About" }</button>
-            </div>
+        <div style={format!("position: relative; width: 100%; height: 100%;{}", theme)}>
+            if *show_chrome {
+                <div class={classes!("hud", hud_class)}>{ "VectorCade" }</div>
+                <div class={classes!("panel", panel_class)}>
+                    <select onchange={on_change} disabled={game_meta.is_empty()}>
+                        if game_meta.is_empty() {
+                            <option>{ "No games available" }</option>
+                        }
+                        { for game_meta.iter().map(|g| {
+                            let broken = GAME_STATE.with(|state| state.borrow().is_unavailable(g.id));
+                            html!{
+                                <option value={g.id} selected={*selected == g.id} disabled={broken}
+                                    title={broken.then_some("Crashed while starting; see the console")}>
+                                    { g.name }
+                                </option>
+                            }
+                        })}
+                    </select>
+                    if caps.coop {
+                        <span class="badge" title="Two players at once">{ "2P" }</span>
+                    }
+                    <button onclick={on_reset}>{ "Reset" }</button>
+                    if *recording {
+                        <span class="rec">{ "\u{25cf} REC" }</span>
+                        <button onclick={on_stop_record}>{ "Stop" }</button>
+                    } else if recorder::supported() {
+                        <select onchange={on_record_secs}>
+                            { for [5u32, 10, 30].iter().map(|s| html!{
+                                <option value={s.to_string()} selected={*record_secs == *s}>
+                                    { format!("{}s", s) }
+                                </option>
+                            })}
+                        </select>
+                        <button onclick={on_record}>{ "Record" }</button>
+                    } else {
+                        <button disabled=true title="Recording needs MediaRecorder, which this browser lacks">
+                            { "Record" }
+                        </button>
+                    }
+                    <button onclick={on_svg} title="Download this frame as SVG">{ "SVG" }</button>
+                    <button onclick={on_settings}>{ "Settings" }</button>
+                    <button onclick={on_stats}>{ "Stats" }</button>
+                    <button onclick={on_about}>{ "About" }</button>
+                </div>
+            } else {
+                <button class={classes!("reveal-tab", panel_class)} title="Show controls (H)"
+                    onclick={on_reveal}>{ "\u{2630}" }</button>
+            }
             <canvas ref={canvas_ref} id="vectorcade-canvas"></canvas>
+            <div class="sr-only" aria-live="polite">{ (*announcement).clone() }</div>
+
+            if *confirm_reset {
+                <div class="dialog-overlay" onclick={on_cancel_reset.clone()}>
+                    <div class="dialog" onclick={Callback::from(|e: web_sys::MouseEvent| e.stop_propagation())}>
+                        <h2>{ "Reset game?" }</h2>
+                        <p class="tagline">{ "The current run will be lost." }</p>
+                        <button class="close-btn" onclick={on_confirm_reset}>{ "Reset" }</button>
+                        { " " }
+                        <button class="close-btn" onclick={on_cancel_reset}>{ "Cancel" }</button>
+                    </div>
+                </div>
+            }
+
+            if *pointer_locked {
+                <div class="lock-hint">{ "Mouse captured \u{2014} Esc to release" }</div>
+            }
+
+            if *graphics_lost {
+                <div class="notice">{ "Restoring graphics..." }</div>
+            }
+
+            if *show_settings {
+                <SettingsDialog on_close={on_close_settings} />
+            }
+
+            if *show_tuning {
+                <TuningPanel />
+            }
+
+            if *show_stats {
+                <div class="dialog-overlay" onclick={on_close_stats.clone()}>
+                    <div class="dialog" onclick={Callback::from(|e: web_sys::MouseEvent| e.stop_propagation())}>
+                        <h2>{ "Stats" }</h2>
+                        <div class="section">
+                            <table>
+                                <tr><th>{ "Game" }</th><th>{ "Plays" }</th><th>{ "Time" }</th><th>{ "Best" }</th></tr>
+                                { for game_meta.iter().map(|g| {
+                                    let s = GAME_STATE.with(|state| state.borrow().stats.get(g.id));
+                                    let best = match s.best_score {
+                                        Some(best) => best.to_string(),
+                                        None if profiles::profile(g.id).capabilities.score => "-".to_string(),
+                                        None => "n/a".to_string(),
+                                    };
+                                    html!{
+                                        <tr>
+                                            <td>{ g.name }</td>
+                                            <td>{ s.plays }</td>
+                                            <td>{ stats::format_duration(s.seconds_played) }</td>
+                                            <td>{ best }</td>
+                                        </tr>
+                                    }
+                                })}
+                            </table>
+                        </div>
+                        <button class="close-btn" onclick={on_close_stats}>{ "Close" }</button>
+                    </div>
+                </div>
+            }
 
             // About dialog
             if *show_about {
@@ -377,6 +1751,18 @@ fn app() -> Html {
                             </table>
                         </div>
 
+                        <div class="section">
+                            <h3>{ "Controls" }</h3>
+                            <table>
+                                { for pad::hints(pad_family).iter().map(|(action, control)| html! {
+                                    <tr><td>{ format!("{}:", action) }</td><td>{ *control }</td></tr>
+                                })}
+                            </table>
+                            { for pad_family.map(|family| html! {
+                                <p>{ format!("{} controller", family.label()) }</p>
+                            })}
+                        </div>
+
                         <div class="section">
                             <h3>{ "Links" }</h3>
                             <ul>
@@ -400,71 +1786,329 @@ fn app() -> Html {
     }
 }
 
-fn start_animation_loop(canvas_ref: NodeRef) {
-    let f: Rc<RefCell<Option<Closure<dyn FnMut(f64)>>>> = Rc::new(RefCell::new(None));
-    let g = f.clone();
+/// Toast text for a game highlighted in the PageUp/PageDown menu.
+fn menu_label(name: &str) -> String {
+    format!("< {} >", name)
+}
+
+/// Create the wgpu renderer for `canvas` at its display size and install it.
+async fn create_renderer(canvas: HtmlCanvasElement, slot: &RefCell<Option<WgpuRenderer>>) -> bool {
+    let window = web_sys::window().expect("no window");
+    let dpr = window.device_pixel_ratio();
+    let rect = canvas.get_bounding_client_rect();
+    let (width, height) = scaling::backing_size(rect.width(), rect.height(), dpr);
+
+    // Set initial canvas size
+    size_canvas(&canvas, width, height, dpr);
 
-    let canvas_ref = canvas_ref.clone();
-    *g.borrow_mut() = Some(Closure::new(move |timestamp: f64| {
-        // Get canvas
-        if let Some(canvas) = canvas_ref.cast::<HtmlCanvasElement>() {
-            let window = web_sys::window().expect("no window");
+    match WgpuRenderer::new_web(canvas.clone(), width, height).await {
+        Ok(renderer) => {
+            *slot.borrow_mut() = Some(renderer);
+            true
+        }
+        Err(e) => {
+            logging::error(&format!("Failed to create wgpu renderer: {:?}", e));
+            false
+        }
+    }
+}
 
-            // Resize canvas to match display size
-            let dpr = window.device_pixel_ratio();
-            let rect = canvas.get_bounding_client_rect();
-            let display_width = (rect.width() * dpr) as u32;
-            let display_height = (rect.height() * dpr) as u32;
+/// Drop the renderer when the GPU context is lost and rebuild it on restore.
+///
+/// The animation loop keeps running and simply skips drawing while the
+/// renderer slot is empty, so games continue to tick through the outage.
+/// `on_lost` hears `true` on loss and `false` once graphics are back.
+fn setup_context_loss_listeners(
+    canvas: &HtmlCanvasElement,
+    renderer: &Rc<RefCell<Option<WgpuRenderer>>>,
+    on_lost: Callback<bool>,
+) -> Listeners {
+    let lost = {
+        let renderer = renderer.clone();
+        let on_lost = on_lost.clone();
+        Closure::<dyn FnMut(web_sys::Event)>::new(move |e: web_sys::Event| {
+            // Prevent default so the browser will attempt a restore.
+            e.prevent_default();
+            logging::warn("graphics context lost");
+            renderer.borrow_mut().take();
+            on_lost.emit(true);
+        })
+    };
+    let restored = {
+        let canvas = canvas.clone();
+        let renderer = renderer.clone();
+        Closure::<dyn FnMut(web_sys::Event)>::new(move |_: web_sys::Event| {
+            let canvas = canvas.clone();
+            let renderer = renderer.clone();
+            let on_lost = on_lost.clone();
+            spawn_local(async move {
+                if create_renderer(canvas, &renderer).await {
+                    logging::info("graphics context restored");
+                    on_lost.emit(false);
+                }
+            });
+        })
+    };
+    let mut listeners = Listeners::default();
+    listeners.listen(canvas, &["webglcontextlost"], lost);
+    listeners.listen(canvas, &["webglcontextrestored"], restored);
+    listeners
+}
 
-            let needs_resize =
-                canvas.width() != display_width || canvas.height() != display_height;
+/// Route key events from `target` into `state`.
+///
+/// With `scoped`, handled events stop propagating so a focused embedded
+/// instance doesn't also drive the page-level one.
+fn setup_key_listeners(
+    target: &web_sys::EventTarget,
+    state: &Rc<RefCell<GameState>>,
+    scoped: bool,
+) -> Listeners {
+    let keydown = {
+        let state = state.clone();
+        Closure::<dyn FnMut(KeyboardEvent)>::new(move |e: KeyboardEvent| {
+            if scoped {
+                e.stop_propagation();
+            }
+            if !e.repeat() && state.borrow_mut().hotkey(&e.code()) {
+                e.prevent_default();
+                return;
+            }
+            let mut state = state.borrow_mut();
+            if let Some(key) = WebInput::map_event(&e, state.settings.key_mapping) {
+                state.input.keyboard.set_key(key, true);
+                state.played_since_reset = true;
+                e.prevent_default();
+            }
+        })
+    };
+    let keyup = {
+        let state = state.clone();
+        Closure::<dyn FnMut(KeyboardEvent)>::new(move |e: KeyboardEvent| {
+            if scoped {
+                e.stop_propagation();
+            }
+            if state.borrow_mut().hotkey_up(&e.code()) {
+                return;
+            }
+            let mut state = state.borrow_mut();
+            if let Some(key) = WebInput::map_event(&e, state.settings.key_mapping) {
+                state.input.keyboard.set_key(key, false);
+            }
+        })
+    };
+    let mut listeners = Listeners::default();
+    listeners.listen(target, &["keydown"], keydown);
+    listeners.listen(target, &["keyup"], keyup);
+    listeners
+}
 
-            if needs_resize {
-                canvas.set_width(display_width);
-                canvas.set_height(display_height);
+/// Track mouse/touch/pen position over the canvas in backing-store pixels.
+///
+/// With the pointer-lock setting on, clicking the canvas captures the mouse
+/// and relative motion moves a virtual aim point instead; Escape releases it.
+/// `on_lock` hears lock changes.
+fn setup_pointer_listeners(
+    canvas: &HtmlCanvasElement,
+    state: &Rc<RefCell<GameState>>,
+    on_lock: Callback<bool>,
+) -> Listeners {
+    let on_pointer = {
+        let state = state.clone();
+        Closure::<dyn FnMut(PointerEvent)>::new(move |e: PointerEvent| {
+            let mut state = state.borrow_mut();
+            if state.input.pointer.is_locked() {
+                let dpr = web_sys::window().map_or(1.0, |w| w.device_pixel_ratio());
+                // Screen Y grows down; NDC Y grows up.
+                let delta = Vec2::new(e.movement_x() as f32, -e.movement_y() as f32)
+                    * (dpr as f32 * overlay::ndc_per_px(&state.screen));
+                state.input.pointer.move_aim(delta, e.buttons() & 1 != 0);
+                return;
+            }
+            let dpr = web_sys::window().map_or(1.0, |w| w.device_pixel_ratio());
+            let pos = Vec2::new(
+                (e.offset_x() as f64 * dpr) as f32,
+                (e.offset_y() as f64 * dpr) as f32,
+            );
+            let pointer = match e.type_().as_str() {
+                "pointerleave" | "pointercancel" => None,
+                "pointerup" => Some((pos, false)),
+                _ => Some((pos, e.buttons() & 1 != 0)),
+            };
+            state.input.pointer.set_pointer_px(pointer);
+            if e.pointer_type() == "touch" && state.settings.touch_controls {
+                let touch = pointer.filter(|(_, down)| *down).map(|(pos, _)| pos);
+                state.touch_controls.set_touch(e.pointer_id(), touch);
+                state.played_since_reset |= touch.is_some();
+            } else if e.pointer_type() == "touch" && state.settings.swipe_gestures {
+                state.handle_swipe(&e);
+            }
+        })
+    };
+    let mut listeners = Listeners::default();
+    listeners.listen(
+        canvas,
+        &[
+            "pointermove",
+            "pointerdown",
+            "pointerup",
+            "pointerleave",
+            "pointercancel",
+        ],
+        on_pointer,
+    );
 
-                // Resize renderer
-                RENDERER.with(|r| {
-                    if let Some(renderer) = r.borrow_mut().as_mut() {
-                        renderer.resize(display_width, display_height);
-                    }
-                });
+    let on_click = {
+        let state = state.clone();
+        let canvas = canvas.clone();
+        Closure::<dyn FnMut()>::new(move || {
+            let state = state.borrow();
+            if state.settings.pointer_lock && !state.input.pointer.is_locked() {
+                canvas.request_pointer_lock();
             }
+        })
+    };
+    listeners.listen(canvas, &["click"], on_click);
 
-            // Update screen info and tick game
-            GAME_STATE.with(|state| {
-                let mut state = state.borrow_mut();
-                state.screen = ScreenInfo {
-                    width_px: display_width,
-                    height_px: display_height,
-                    dpi_scale: dpr as f32,
-                };
-                state.tick(timestamp);
-
-                // Render using wgpu
-                RENDERER.with(|r| {
-                    if let Some(renderer) = r.borrow_mut().as_mut() {
-                        renderer.render(&state.draw_cmds);
-                    }
-                });
-            });
-        }
+    let on_lock_change = {
+        let state = state.clone();
+        let canvas = canvas.clone();
+        Closure::<dyn FnMut()>::new(move || {
+            let locked = web_sys::window()
+                .and_then(|w| w.document())
+                .and_then(|d| d.pointer_lock_element())
+                .is_some_and(|el| &el == canvas.as_ref());
+            state.borrow_mut().input.pointer.set_locked(locked);
+            on_lock.emit(locked);
+        })
+    };
+    if let Some(document) = web_sys::window().and_then(|w| w.document()) {
+        listeners.listen(&document, &["pointerlockchange"], on_lock_change);
+    }
+    listeners
+}
+
+/// Give `canvas` a `width` × `height` backing store and pin its CSS box to
+/// the same size at `dpr`, so each backing pixel covers one device pixel.
+fn size_canvas(canvas: &HtmlCanvasElement, width: u32, height: u32, dpr: f64) {
+    canvas.set_width(width);
+    canvas.set_height(height);
+    let style = canvas.style();
+    for (prop, px) in [("width", width), ("height", height)] {
+        let _ = style.set_property(prop, &format!("{}px", px as f64 / dpr));
+    }
+}
+
+/// Drop the box `size_canvas` pinned, so the stylesheet lays the canvas out
+/// again for measuring.
+fn unpin_canvas(canvas: &HtmlCanvasElement) {
+    let style = canvas.style();
+    for prop in ["width", "height"] {
+        let _ = style.remove_property(prop);
+    }
+}
 
-        // Schedule next frame
+/// Start the per-frame resize/tick/render loop for one instance. Dropping or
+/// stopping the returned handle cancels it.
+fn start_animation_loop(canvas: HtmlCanvasElement, instance: Instance) -> FrameLoop {
+    // Window size and ratio as of the last layout.
+    let mut viewport = (0.0, 0.0, 0.0);
+    FrameLoop::start(move |timestamp: f64| {
         let window = web_sys::window().expect("no window");
-        window
-            .request_animation_frame(f.borrow().as_ref().unwrap().as_ref().unchecked_ref())
-            .unwrap();
-    }));
 
-    // Start the loop
-    let window = web_sys::window().expect("no window");
-    window
-        .request_animation_frame(g.borrow().as_ref().unwrap().as_ref().unchecked_ref())
-        .unwrap();
+        // Resize canvas to match display size. Its box is pinned between
+        // window resizes, so it is only laid out afresh after one.
+        let dpr = window.device_pixel_ratio();
+        let inner = |v: Result<JsValue, JsValue>| v.ok().and_then(|v| v.as_f64()).unwrap_or(0.0);
+        let now_viewport = (
+            inner(window.inner_width()),
+            inner(window.inner_height()),
+            dpr,
+        );
+        let relayout = now_viewport != viewport;
+        if relayout {
+            viewport = now_viewport;
+            unpin_canvas(&canvas);
+        }
+        let rect = canvas.get_bounding_client_rect();
+        let (display_width, display_height) =
+            scaling::backing_size(rect.width(), rect.height(), dpr);
+
+        let needs_resize = canvas.width() != display_width || canvas.height() != display_height;
+        if relayout || needs_resize {
+            size_canvas(&canvas, display_width, display_height, dpr);
+        }
+
+        if needs_resize {
+            logging::debug(&format!("resize to {}x{}", display_width, display_height));
+
+            // Resize renderer
+            if let Some(renderer) = instance.renderer.borrow_mut().as_mut() {
+                renderer.resize(display_width, display_height);
+            }
+        }
+
+        // Update screen info and tick game
+        let mut state = instance.state.borrow_mut();
+        state
+            .input
+            .gamepad
+            .set_analog(input::poll_gamepad().unwrap_or_default());
+        let GameState {
+            pad_buttons, input, ..
+        } = &mut *state;
+        pad_buttons.poll(&mut input.keyboard);
+        state.check_dpr(dpr as f32);
+        state.screen = ScreenInfo {
+            width_px: display_width,
+            height_px: display_height,
+            dpi_scale: dpr as f32,
+        };
+        let view = ViewTransform::new(&state.screen, state.playfield_scale());
+        state.input.pointer.resolve_pointer(|px| view.from_px(px));
+        // The controls are drawn over the scaled playfield, so no `scale`.
+        let unscaled = ViewTransform::new(&state.screen, 1.0);
+        let GameState {
+            touch_controls,
+            input,
+            ..
+        } = &mut *state;
+        touch_controls.update(&mut input.keyboard, |px| unscaled.from_px(px));
+        if !state.tick(timestamp) {
+            return;
+        }
+
+        // Render using wgpu
+        if let Some(renderer) = instance.renderer.borrow_mut().as_mut() {
+            renderer.render(&state.draw_cmds);
+        }
+        state.frame_dump.capture(&canvas);
+    })
 }
 
 fn main() {
-    console_error_panic_hook::set_once();
+    boot_error::install();
+    logging::init_from_url();
     yew::Renderer::<App>::new().render();
 }
+
+// Browser-only: the state reads storage and the page URL.
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn tick_without_games_says_so() {
+        let mut state = GameState::with_games(Vec::new(), false);
+        for frame in 0..3 {
+            state.tick(frame as f64 * 16.7);
+        }
+        assert!(state.draw_cmds.iter().any(|cmd| matches!(
+            cmd,
+            DrawCmd::Text { text, .. } if text == "NO GAMES AVAILABLE"
+        )));
+    }
+}
@@ -0,0 +1,150 @@
+//! Playfield rotation in quarter turns, and mirroring.
+//!
+//! The game's draw list is rotated counter-clockwise after it renders, then
+//! mirrored, and `RotatedInput` maps input back the other way so "up" on
+//! screen is still up for the player.
+
+use glam::Vec2;
+use vectorcade_shared::draw::DrawCmd;
+use vectorcade_shared::input::{Axis, Button, InputState, Key, Pointer};
+
+use crate::display_list;
+
+/// Rotate `p` counter-clockwise by `turns` quarter turns about the origin.
+pub fn rotate(p: Vec2, turns: u8) -> Vec2 {
+    match turns % 4 {
+        0 => p,
+        1 => Vec2::new(-p.y, p.x),
+        2 => -p,
+        _ => Vec2::new(p.y, -p.x),
+    }
+}
+
+/// Rotation followed by optional horizontal/vertical mirroring.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Orientation {
+    pub turns: u8,
+    pub flip_x: bool,
+    pub flip_y: bool,
+}
+
+impl Orientation {
+    /// Game point to screen point.
+    pub fn apply(self, p: Vec2) -> Vec2 {
+        self.mirror(rotate(p, self.turns))
+    }
+
+    /// Screen vector to game vector.
+    pub fn unapply(self, v: Vec2) -> Vec2 {
+        rotate(self.mirror(v), 4 - self.turns % 4)
+    }
+
+    fn mirror(self, p: Vec2) -> Vec2 {
+        Vec2::new(
+            if self.flip_x { -p.x } else { p.x },
+            if self.flip_y { -p.y } else { p.y },
+        )
+    }
+
+    fn is_identity(self) -> bool {
+        self.turns % 4 == 0 && !self.flip_x && !self.flip_y
+    }
+}
+
+/// Rotate and mirror every point in the display list.
+///
+/// Text anchors move but glyphs stay upright and unmirrored; the renderer
+/// has no text transforms yet.
+pub fn orient_cmds(cmds: &mut [DrawCmd], orientation: Orientation) {
+    if !orientation.is_identity() {
+        display_list::map_points(cmds, |p| orientation.apply(p));
+    }
+}
+
+/// Unit direction of an arrow key.
+fn direction(k: Key) -> Option<Vec2> {
+    match k {
+        Key::Up => Some(Vec2::Y),
+        Key::Down => Some(Vec2::NEG_Y),
+        Key::Left => Some(Vec2::NEG_X),
+        Key::Right => Some(Vec2::X),
+        _ => None,
+    }
+}
+
+/// Arrow key pointing along a unit axis direction.
+fn arrow_key(dir: Vec2) -> Key {
+    if dir.y > 0.5 {
+        Key::Up
+    } else if dir.y < -0.5 {
+        Key::Down
+    } else if dir.x < 0.0 {
+        Key::Left
+    } else {
+        Key::Right
+    }
+}
+
+/// Presents screen-relative input in the oriented game's own frame.
+pub struct RotatedInput<'a> {
+    inner: &'a dyn InputState,
+    orientation: Orientation,
+    /// Negate the game's vertical stick axis (the "invert Y" preference).
+    invert_y: bool,
+}
+
+impl<'a> RotatedInput<'a> {
+    pub fn new(inner: &'a dyn InputState, orientation: Orientation) -> Self {
+        Self {
+            inner,
+            orientation,
+            invert_y: false,
+        }
+    }
+
+    /// Invert `MoveY`. Applied in the game's frame, after rotation and flips
+    /// are undone, so it always reverses the game's own up/down whatever the
+    /// screen orientation.
+    pub fn with_invert_y(mut self, invert_y: bool) -> Self {
+        self.invert_y = invert_y;
+        self
+    }
+
+    /// Screen vector to game vector.
+    fn unrotate(&self, v: Vec2) -> Vec2 {
+        self.orientation.unapply(v)
+    }
+}
+
+impl InputState for RotatedInput<'_> {
+    fn key(&self, k: Key) -> Button {
+        // The game's direction appears on screen rotated and mirrored; read
+        // the key the player presses for that on-screen direction.
+        match direction(k) {
+            Some(dir) => self.inner.key(arrow_key(self.orientation.apply(dir))),
+            None => self.inner.key(k),
+        }
+    }
+
+    fn axis(&self, a: Axis) -> f32 {
+        let stick = || {
+            self.unrotate(Vec2::new(
+                self.inner.axis(Axis::MoveX),
+                self.inner.axis(Axis::MoveY),
+            ))
+        };
+        match a {
+            Axis::MoveX => stick().x,
+            Axis::MoveY if self.invert_y => -stick().y,
+            Axis::MoveY => stick().y,
+            _ => self.inner.axis(a),
+        }
+    }
+
+    fn pointer(&self) -> Option<Pointer> {
+        self.inner.pointer().map(|p| Pointer {
+            pos: self.unrotate(p.pos),
+            down: p.down,
+        })
+    }
+}
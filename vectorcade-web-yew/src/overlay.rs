@@ -0,0 +1,285 @@
+//! Platform overlays drawn through the shared `DrawCmd` display list.
+//!
+//! These helpers let the shell draw its own vector text and lines on top of
+//! (or instead of) game output, so overlays share the renderer's transform
+//! and look like the games they sit on.
+
+use glam::Vec2;
+use vectorcade_shared::draw::{DrawCmd, Line2, Stroke};
+use vectorcade_shared::font::FontStyleId;
+use vectorcade_shared::game::ScreenInfo;
+use vectorcade_shared::Rgba;
+
+/// Approximate glyph advance as a fraction of the text size.
+const GLYPH_ADVANCE: f32 = 0.8;
+/// Line advance for vertical text, as a fraction of the text size.
+pub const LINE_ADVANCE: f32 = 1.2;
+
+/// Which way text runs from its anchor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextDirection {
+    /// Left edge at the anchor, running right.
+    #[default]
+    Ltr,
+    /// Right edge at the anchor; glyphs are placed from the right using the
+    /// measured width.
+    Rtl,
+    /// One glyph per row, top glyph at the anchor, running down.
+    TopToBottom,
+}
+
+/// Append a single stroked line in NDC space.
+pub fn line(out: &mut Vec<DrawCmd>, a: Vec2, b: Vec2, color: Rgba, width_px: f32) {
+    out.push(DrawCmd::Line(Line2 {
+        a,
+        b,
+        stroke: Stroke {
+            color,
+            width_px,
+            glow: 0.0,
+        },
+    }));
+}
+
+/// Append a polyline in NDC space.
+pub fn polyline(out: &mut Vec<DrawCmd>, pts: Vec<Vec2>, closed: bool, color: Rgba, width_px: f32) {
+    out.push(DrawCmd::Polyline {
+        pts,
+        closed,
+        stroke: Stroke {
+            color,
+            width_px,
+            glow: 0.0,
+        },
+    });
+}
+
+/// A dash pattern in pixels: `on` drawn, `off` skipped, shifted `offset`
+/// along the path.
+#[derive(Clone, Copy, Debug)]
+pub struct Dash {
+    pub on: f32,
+    pub off: f32,
+    pub offset: f32,
+}
+
+/// Append a dashed path in NDC.
+///
+/// Each dash is its own line, so a dash running round a corner is cut
+/// there and continues on the next segment.
+pub fn dashed(
+    out: &mut Vec<DrawCmd>,
+    screen: &ScreenInfo,
+    pts: &[Vec2],
+    closed: bool,
+    pattern: Dash,
+    color: Rgba,
+    width_px: f32,
+) {
+    let px = ndc_per_px(screen);
+    let (dash, period) = (pattern.on * px, (pattern.on + pattern.off).max(1.0) * px);
+    // Distance along the path where the current pattern period started.
+    let mut phase = -(pattern.offset * px).rem_euclid(period);
+    let mut travelled = 0.0;
+    let closing = match (pts.first(), pts.last()) {
+        (Some(&first), Some(&last)) if closed && pts.len() > 2 => Some([last, first]),
+        _ => None,
+    };
+    let segments = pts.windows(2).map(|w| [w[0], w[1]]).chain(closing);
+    for [a, b] in segments {
+        let len = a.distance(b);
+        let end = travelled + len;
+        while phase < end {
+            let on = (phase.max(travelled), (phase + dash).min(end));
+            if on.1 > on.0 {
+                let at = |d: f32| a.lerp(b, (d - travelled) / len.max(f32::EPSILON));
+                line(out, at(on.0), at(on.1), color, width_px);
+            }
+            if phase + period >= end {
+                break;
+            }
+            phase += period;
+        }
+        travelled = end;
+    }
+}
+
+/// Append an animated "marching ants" rectangle outline (NDC); the dashes
+/// advance with `now_s`.
+pub fn marching_ants(
+    out: &mut Vec<DrawCmd>,
+    screen: &ScreenInfo,
+    min: Vec2,
+    max: Vec2,
+    now_s: f64,
+    color: Rgba,
+) {
+    const SPEED_PX_PER_S: f64 = 20.0;
+    let (on, off) = (6.0, 4.0);
+    let offset = (now_s * SPEED_PX_PER_S % (on + off) as f64) as f32;
+    let pts = [min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)];
+    dashed(
+        out,
+        screen,
+        &pts,
+        true,
+        Dash { on, off, offset },
+        color,
+        1.5,
+    );
+}
+
+/// Append an axis-aligned rectangle outline between two corners (NDC).
+pub fn rect(out: &mut Vec<DrawCmd>, min: Vec2, max: Vec2, color: Rgba, width_px: f32) {
+    let pts = vec![min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)];
+    polyline(out, pts, true, color, width_px);
+}
+
+/// Append a glowing rounded-rectangle outline (NDC).
+pub fn rounded_rect(out: &mut Vec<DrawCmd>, min: Vec2, max: Vec2, radius: f32, stroke: Stroke) {
+    const SEGMENTS: usize = 6;
+    let r = radius.min((max.x - min.x) * 0.5).min((max.y - min.y) * 0.5);
+    // Corner centers in counter-clockwise order, with each arc's start angle.
+    let corners = [
+        (Vec2::new(max.x - r, min.y + r), -0.5),
+        (Vec2::new(max.x - r, max.y - r), 0.0),
+        (Vec2::new(min.x + r, max.y - r), 0.5),
+        (Vec2::new(min.x + r, min.y + r), 1.0),
+    ];
+    let mut pts = Vec::with_capacity(corners.len() * (SEGMENTS + 1));
+    for (center, start) in corners {
+        for i in 0..=SEGMENTS {
+            let t = (start + 0.5 * i as f32 / SEGMENTS as f32) * std::f32::consts::PI;
+            pts.push(center + Vec2::new(t.cos(), t.sin()) * r);
+        }
+    }
+    out.push(DrawCmd::Polyline {
+        pts,
+        closed: true,
+        stroke,
+    });
+}
+
+/// Append vector text with its left edge at `pos` (NDC).
+pub fn text(out: &mut Vec<DrawCmd>, pos: Vec2, s: &str, size_px: f32, color: Rgba) {
+    out.push(DrawCmd::Text {
+        pos,
+        text: s.to_string(),
+        size_px,
+        color,
+        style: FontStyleId::ATARI,
+    });
+}
+
+/// Append vector text horizontally centered on `center` (NDC).
+pub fn text_centered(
+    out: &mut Vec<DrawCmd>,
+    screen: &ScreenInfo,
+    center: Vec2,
+    s: &str,
+    size_px: f32,
+    color: Rgba,
+) {
+    let half = text_width(screen, s, size_px) * 0.5;
+    text(out, center - Vec2::new(half, 0.0), s, size_px, color);
+}
+
+/// Append vector text laid out in `dir` from `pos` (NDC).
+///
+/// `DrawCmd::Text` only runs left to right, so the other directions are
+/// built here: RTL as one right-aligned command, vertical as one command per
+/// glyph.
+pub fn text_directed(
+    out: &mut Vec<DrawCmd>,
+    screen: &ScreenInfo,
+    pos: Vec2,
+    s: &str,
+    size_px: f32,
+    color: Rgba,
+    dir: TextDirection,
+) {
+    match dir {
+        TextDirection::Ltr => text(out, pos, s, size_px, color),
+        TextDirection::Rtl => {
+            let w = text_extent(screen, s, size_px, dir).x;
+            text(out, pos - Vec2::new(w, 0.0), s, size_px, color);
+        }
+        TextDirection::TopToBottom => {
+            let step = size_px * LINE_ADVANCE * ndc_per_px(screen);
+            let mut buf = [0u8; 4];
+            for (i, c) in s.chars().enumerate() {
+                let glyph: &str = c.encode_utf8(&mut buf);
+                let at = pos - Vec2::new(0.0, step * i as f32);
+                text(out, at, glyph, size_px, color);
+            }
+        }
+    }
+}
+
+/// Extent of `s` laid out in `dir`, in NDC units (width, height).
+pub fn text_extent(screen: &ScreenInfo, s: &str, size_px: f32, dir: TextDirection) -> Vec2 {
+    let n = s.chars().count() as f32;
+    let px = ndc_per_px(screen);
+    match dir {
+        TextDirection::Ltr | TextDirection::Rtl => {
+            Vec2::new(text_width(screen, s, size_px), size_px * px)
+        }
+        TextDirection::TopToBottom => Vec2::new(
+            size_px * GLYPH_ADVANCE * px,
+            n * size_px * LINE_ADVANCE * px,
+        ),
+    }
+}
+
+/// Estimated width of a string in NDC units for the given screen.
+pub fn text_width(screen: &ScreenInfo, s: &str, size_px: f32) -> f32 {
+    let px = s.chars().count() as f32 * size_px * GLYPH_ADVANCE;
+    px * ndc_per_px(screen)
+}
+
+/// NDC units spanned by one pixel along the shorter screen axis.
+pub fn ndc_per_px(screen: &ScreenInfo) -> f32 {
+    let short = screen.width_px.min(screen.height_px).max(1) as f32;
+    2.0 / short
+}
+
+/// Draw the NDC `[-1, 1]` bounds, the axes through the origin, tick marks,
+/// and axis labels.
+pub fn ndc_wireframe(out: &mut Vec<DrawCmd>, screen: &ScreenInfo) {
+    let color = Rgba(0.3, 0.4, 0.6, 0.6);
+    rect(out, Vec2::splat(-1.0), Vec2::splat(1.0), color, 1.0);
+    line(out, Vec2::new(-1.0, 0.0), Vec2::new(1.0, 0.0), color, 1.0);
+    line(out, Vec2::new(0.0, -1.0), Vec2::new(0.0, 1.0), color, 1.0);
+
+    const TICK: f32 = 0.02;
+    for i in -4..=4 {
+        if i == 0 {
+            continue;
+        }
+        let t = i as f32 * 0.25;
+        line(out, Vec2::new(t, -TICK), Vec2::new(t, TICK), color, 1.0);
+        line(out, Vec2::new(-TICK, t), Vec2::new(TICK, t), color, 1.0);
+    }
+
+    const LABEL_PX: f32 = 12.0;
+    let x_label = Vec2::new(0.98, 0.03);
+    text_directed(
+        out,
+        screen,
+        x_label,
+        "+X",
+        LABEL_PX,
+        color,
+        TextDirection::Rtl,
+    );
+    let y_label = Vec2::new(0.03, 0.95);
+    text_directed(
+        out,
+        screen,
+        y_label,
+        "+Y",
+        LABEL_PX,
+        color,
+        TextDirection::TopToBottom,
+    );
+}
@@ -0,0 +1,101 @@
+//! Per-game settings overrides, layered over the global settings.
+//!
+//! Each game keeps only the fields set for it, as JSON keyed by field name,
+//! so a later change to the global value still reaches every field the game
+//! hasn't overridden. Fields that no longer exist are ignored on load, as
+//! for the global settings.
+
+use std::collections::HashMap;
+
+use serde_json::{Map, Value};
+
+use crate::logging;
+use crate::settings::Settings;
+use crate::storage;
+
+/// `localStorage` key for the overrides of every game.
+const STORAGE_KEY: &str = "vectorcade.settings.games";
+
+type Fields = Map<String, Value>;
+
+fn fields(settings: &Settings) -> Fields {
+    match serde_json::to_value(settings) {
+        Ok(Value::Object(map)) => map,
+        _ => Fields::new(),
+    }
+}
+
+/// The fields whose values differ between `prev` and `next`.
+pub fn changes(prev: &Settings, next: &Settings) -> Fields {
+    let prev = fields(prev);
+    fields(next)
+        .into_iter()
+        .filter(|(k, v)| prev.get(k) != Some(v))
+        .collect()
+}
+
+/// `base` with `changes` written over it.
+pub fn apply(base: &Settings, changes: &Fields) -> Settings {
+    let mut merged = fields(base);
+    merged.extend(changes.clone());
+    serde_json::from_value(Value::Object(merged)).unwrap_or_else(|e| {
+        logging::warn(&format!("ignoring unreadable settings override: {}", e));
+        base.clone()
+    })
+}
+
+#[derive(Default)]
+pub struct Overrides {
+    games: HashMap<String, Fields>,
+}
+
+impl Overrides {
+    pub fn load() -> Self {
+        Self {
+            games: storage::load(STORAGE_KEY).unwrap_or_default(),
+        }
+    }
+
+    /// Settings for game `id`: `global` with the game's overrides on top.
+    pub fn resolve(&self, global: &Settings, id: &str) -> Settings {
+        match self.games.get(id) {
+            Some(changes) => apply(global, changes),
+            None => global.clone(),
+        }
+    }
+
+    /// Override `changes` for game `id`. Fields that end up equal to the
+    /// global value are dropped rather than pinned.
+    pub fn merge(&mut self, id: &str, changes: Fields, global: &Settings) {
+        let global = fields(global);
+        let game = self.games.entry(id.to_string()).or_default();
+        game.extend(changes);
+        game.retain(|k, v| global.get(k) != Some(v));
+        if game.is_empty() {
+            self.games.remove(id);
+        }
+        self.save();
+    }
+
+    /// Drop every override for game `id`.
+    pub fn clear(&mut self, id: &str) {
+        if self.games.remove(id).is_some() {
+            self.save();
+        }
+    }
+
+    /// Names of the fields game `id` overrides, sorted.
+    pub fn overridden(&self, id: &str) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .games
+            .get(id)
+            .map(|game| game.keys().cloned().collect())
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+
+    fn save(&self) {
+        storage::save(STORAGE_KEY, &self.games);
+    }
+}
@@ -0,0 +1,119 @@
+//! Gamepad face buttons and their labels on the connected controller.
+//!
+//! The browser only reports a free-form `id` string for each pad, so the
+//! controller family is guessed from well-known names and USB vendor ids.
+//! Control hints use the family's button names ("A" on Xbox, "Cross" on
+//! PlayStation) and fall back to keyboard labels when no pad is connected.
+
+use vectorcade_shared::input::Key;
+use wasm_bindgen::JsCast;
+
+use crate::input::KeyboardInput;
+
+/// Standard-mapping button indices and the keys they press.
+const BUTTON_KEYS: [(u32, Key); 2] = [(0, Key::Space), (9, Key::Enter)];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PadFamily {
+    Xbox,
+    PlayStation,
+    Nintendo,
+    Generic,
+}
+
+impl PadFamily {
+    /// Guess the family from a `Gamepad.id` string.
+    pub fn from_id(id: &str) -> Self {
+        let id = id.to_ascii_lowercase();
+        let has = |needles: &[&str]| needles.iter().any(|n| id.contains(n));
+        if has(&["xbox", "xinput", "045e"]) {
+            Self::Xbox
+        } else if has(&["playstation", "dualshock", "dualsense", "054c"]) {
+            Self::PlayStation
+        } else if has(&["nintendo", "switch", "joy-con", "057e"]) {
+            Self::Nintendo
+        } else {
+            Self::Generic
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Xbox => "Xbox",
+            Self::PlayStation => "PlayStation",
+            Self::Nintendo => "Nintendo",
+            Self::Generic => "Gamepad",
+        }
+    }
+
+    /// Name of the bottom face button (standard button 0).
+    fn south(self) -> &'static str {
+        match self {
+            Self::Xbox => "A",
+            Self::PlayStation => "Cross",
+            Self::Nintendo => "B",
+            Self::Generic => "Button 1",
+        }
+    }
+
+    /// Name of the right-hand center button (standard button 9).
+    fn start(self) -> &'static str {
+        match self {
+            Self::Xbox => "Menu",
+            Self::PlayStation => "Options",
+            Self::Nintendo => "+",
+            Self::Generic => "Start",
+        }
+    }
+}
+
+/// Control hints as (action, control) rows for `pad`, or for the keyboard
+/// when no pad is connected.
+pub fn hints(pad: Option<PadFamily>) -> [(&'static str, &'static str); 3] {
+    match pad {
+        Some(pad) => [
+            ("Move", "Left stick"),
+            ("Fire", pad.south()),
+            ("Start", pad.start()),
+        ],
+        None => [
+            ("Move", "Arrows / WASD"),
+            ("Fire", "Space"),
+            ("Start", "Enter"),
+        ],
+    }
+}
+
+fn first_pad() -> Option<web_sys::Gamepad> {
+    let pads = web_sys::window()?.navigator().get_gamepads().ok()?;
+    pads.iter()
+        .find_map(|p| p.dyn_into::<web_sys::Gamepad>().ok())
+}
+
+/// Family of the first connected gamepad, if any.
+pub fn connected() -> Option<PadFamily> {
+    first_pad().map(|pad| PadFamily::from_id(&pad.id()))
+}
+
+/// Feeds the first pad's fire and start buttons into the keyboard state.
+#[derive(Default)]
+pub struct PadButtons {
+    held: [bool; BUTTON_KEYS.len()],
+}
+
+impl PadButtons {
+    /// Press or release the mapped keys whose buttons changed since the last poll.
+    pub fn poll(&mut self, keyboard: &mut KeyboardInput) {
+        let buttons = first_pad().map(|pad| pad.buttons());
+        for ((index, key), held) in BUTTON_KEYS.into_iter().zip(&mut self.held) {
+            let down = buttons
+                .as_ref()
+                .and_then(|b| b.get(index).dyn_into::<web_sys::GamepadButton>().ok())
+                .is_some_and(|b| b.pressed());
+            if down != *held {
+                keyboard.set_key(key, down);
+                *held = down;
+            }
+        }
+    }
+}
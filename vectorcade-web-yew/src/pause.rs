@@ -0,0 +1,79 @@
+//! Manual pause and the "3-2-1" countdown before play resumes.
+//!
+//! The game stays frozen while paused and while counting down. Keys pressed
+//! meanwhile still land in the input state, so whatever is held when the
+//! countdown ends applies on the first live frame.
+//!
+//! After `SLEEP_AFTER_MS` paused the page-level frame loop stops to save
+//! power, leaving the PAUSED frame on screen; unpausing restarts it.
+
+use glam::Vec2;
+use vectorcade_shared::draw::DrawCmd;
+use vectorcade_shared::game::ScreenInfo;
+use vectorcade_shared::Rgba;
+
+use crate::overlay;
+
+/// How long a pause lasts before the frame loop stops (ms).
+pub const SLEEP_AFTER_MS: f64 = 30_000.0;
+
+#[derive(Default)]
+pub struct Pause {
+    paused: bool,
+    /// First frame seen paused (ms).
+    paused_since: Option<f64>,
+    /// A countdown starts on the next frame.
+    armed: bool,
+    /// When the countdown ends (ms).
+    resume_at: Option<f64>,
+}
+
+impl Pause {
+    pub fn toggle(&mut self) {
+        if self.paused {
+            self.resume();
+        } else {
+            self.paused = true;
+            self.paused_since = None;
+            self.resume_at = None;
+        }
+    }
+
+    /// Leave the pause (manual or because the page was hidden) through a
+    /// countdown.
+    pub fn resume(&mut self) {
+        self.paused = false;
+        self.armed = true;
+    }
+
+    /// Advance the countdown at `now`. Returns true while the game should
+    /// stay frozen.
+    pub fn frozen(&mut self, now: f64, countdown_s: f32) -> bool {
+        if std::mem::take(&mut self.armed) && countdown_s > 0.0 {
+            self.resume_at = Some(now + countdown_s as f64 * 1000.0);
+        }
+        if self.resume_at.is_some_and(|t| now >= t) {
+            self.resume_at = None;
+        }
+        if self.paused {
+            self.paused_since.get_or_insert(now);
+        }
+        self.paused || self.resume_at.is_some()
+    }
+
+    /// Whether the pause has lasted long enough to stop the frame loop.
+    pub fn is_long(&self, now: f64) -> bool {
+        self.paused && self.paused_since.is_some_and(|t| now - t >= SLEEP_AFTER_MS)
+    }
+
+    pub fn draw(&self, now: f64, screen: &ScreenInfo, out: &mut Vec<DrawCmd>) {
+        let color = Rgba(1.0, 1.0, 1.0, 1.0);
+        if self.paused {
+            overlay::text_centered(out, screen, Vec2::ZERO, "PAUSED", 36.0, color);
+        } else if let Some(resume_at) = self.resume_at {
+            let remaining = ((resume_at - now) / 1000.0).ceil().max(1.0) as u32;
+            let count = remaining.to_string();
+            overlay::text_centered(out, screen, Vec2::ZERO, &count, 72.0, color);
+        }
+    }
+}
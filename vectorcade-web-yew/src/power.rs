@@ -0,0 +1,86 @@
+//! Battery-aware low-power mode.
+//!
+//! Reads `navigator.getBattery()` (through `Reflect`, as it is missing on
+//! Safari and unstable in `web-sys`) and reports when the device is
+//! discharging below `LOW_BATTERY`. While low power is active the frame loop
+//! drops glow and caps the frame rate.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use js_sys::{Function, Promise, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+
+use crate::logging;
+
+/// Battery level (0..1) below which a discharging device goes low power.
+const LOW_BATTERY: f64 = 0.3;
+/// Frame rate cap while low power is active.
+pub const LOW_POWER_HZ: f64 = 30.0;
+
+/// Battery state shared with the listeners that keep it current.
+#[derive(Clone, Default)]
+pub struct Battery {
+    low: Rc<Cell<bool>>,
+}
+
+impl Battery {
+    /// Start watching the battery. Stays "not low" where the API is missing.
+    pub fn watch() -> Self {
+        let battery = Self::default();
+        let low = battery.low.clone();
+        spawn_local(async move {
+            if let Err(e) = listen(low).await {
+                logging::debug(&format!("battery status unavailable: {:?}", e));
+            }
+        });
+        battery
+    }
+
+    /// Discharging and below the threshold.
+    pub fn is_low(&self) -> bool {
+        self.low.get()
+    }
+}
+
+async fn listen(low: Rc<Cell<bool>>) -> Result<(), JsValue> {
+    let navigator = web_sys::window()
+        .ok_or_else(|| JsValue::from_str("no window"))?
+        .navigator();
+    let get_battery: Function = Reflect::get(&navigator, &"getBattery".into())?.dyn_into()?;
+    let manager = JsFuture::from(get_battery.call0(&navigator)?.dyn_into::<Promise>()?).await?;
+
+    let update = {
+        let manager = manager.clone();
+        move || {
+            let charging = Reflect::get(&manager, &"charging".into())
+                .ok()
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let level = Reflect::get(&manager, &"level".into())
+                .ok()
+                .and_then(|v| v.as_f64())
+                .unwrap_or(1.0);
+            let now_low = !charging && level < LOW_BATTERY;
+            if low.replace(now_low) != now_low {
+                logging::info(&format!(
+                    "battery {:.0}%{}; low power {}",
+                    level * 100.0,
+                    if charging { " charging" } else { "" },
+                    if now_low { "on" } else { "off" }
+                ));
+            }
+        }
+    };
+    update();
+    let on_change = Closure::<dyn Fn()>::new(update);
+    let target: web_sys::EventTarget = manager.dyn_into()?;
+    target.add_event_listener_with_callback("levelchange", on_change.as_ref().unchecked_ref())?;
+    target
+        .add_event_listener_with_callback("chargingchange", on_change.as_ref().unchecked_ref())?;
+    // The battery outlives any one game; keep listening for the page's life.
+    on_change.forget();
+    Ok(())
+}
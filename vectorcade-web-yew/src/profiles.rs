@@ -0,0 +1,111 @@
+//! Platform-side per-game presentation profiles.
+//!
+//! `GameMeta` only carries what games declare about themselves. Presentation
+//! choices the shell makes per game live here, keyed by `GameMeta::id`.
+
+use crate::crosshair::Crosshair;
+use crate::devices::{Device, Need};
+use crate::query;
+
+/// How a game's frames are cleared.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ClearPolicy {
+    /// Every frame starts cleared; the platform adds a clear if the game
+    /// emits none.
+    #[default]
+    AlwaysClear,
+    /// Frames accumulate (oscilloscope or light-painting games); any clear
+    /// the game emits is dropped.
+    NeverClear,
+    /// The game's own `Clear` commands are passed through as they are.
+    GameControlled,
+}
+
+impl ClearPolicy {
+    /// Parse a `?clear=` value: `always`, `never`, or `game`.
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "always" => Some(Self::AlwaysClear),
+            "never" => Some(Self::NeverClear),
+            "game" => Some(Self::GameControlled),
+            _ => None,
+        }
+    }
+}
+
+/// Optional features a game implements, so the UI only offers those.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Reports a score.
+    pub score: bool,
+    /// Supports two players at once.
+    pub coop: bool,
+}
+
+/// Per-game platform behavior.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GameProfile {
+    /// Game opts in to the global palette-cycle effect.
+    pub palette_cycle: bool,
+    /// Quarter turns the game prefers, added to the user's rotation
+    /// (vertical games would use 1).
+    pub quarter_turns: u8,
+    pub clear_policy: ClearPolicy,
+    /// Relative chance of being picked in attract mode; 0 keeps the game
+    /// out of the showcase (it stays selectable).
+    pub attract_weight: f32,
+    /// Color for the HUD and panel chrome; `None` keeps the default.
+    pub theme: Option<[f32; 3]>,
+    /// Crosshair drawn at the pointer, for mouse-aimed games.
+    pub crosshair: Option<Crosshair>,
+    /// Input devices the game needs beyond the keyboard.
+    pub needs: &'static [(Device, Need)],
+    /// Stands in for `Game::capabilities()` until games declare it.
+    pub capabilities: Capabilities,
+}
+
+impl Default for GameProfile {
+    fn default() -> Self {
+        Self {
+            palette_cycle: false,
+            quarter_turns: 0,
+            clear_policy: ClearPolicy::default(),
+            attract_weight: 1.0,
+            theme: None,
+            crosshair: None,
+            needs: &[],
+            capabilities: Capabilities::default(),
+        }
+    }
+}
+
+/// Look up the profile for a game id, falling back to defaults.
+///
+/// `?clear=always|never|game` overrides the clear policy for every game,
+/// for trying accumulation effects; `?crosshair=cross|circle|dot` adds a
+/// crosshair to every game.
+pub fn profile(id: &str) -> GameProfile {
+    let mut profile = match id {
+        "asteroids" => GameProfile {
+            theme: Some([1.0, 1.0, 1.0]),
+            ..GameProfile::default()
+        },
+        "battlezone" => GameProfile {
+            theme: Some([0.3, 1.0, 0.3]),
+            ..GameProfile::default()
+        },
+        "tempest" => GameProfile {
+            palette_cycle: true,
+            theme: Some([0.2, 0.9, 1.0]),
+            ..GameProfile::default()
+        },
+        _ => GameProfile::default(),
+    };
+    if let Some(policy) = query::param("clear").and_then(|v| ClearPolicy::parse(&v)) {
+        profile.clear_policy = policy;
+    }
+    if let Some(crosshair) = query::param("crosshair").and_then(|v| Crosshair::parse(&v)) {
+        profile.crosshair = Some(crosshair);
+    }
+    profile
+}
@@ -0,0 +1,146 @@
+//! Adaptive glow: trade glow for frame rate on heavy scenes.
+//!
+//! Glow is the expensive part of a frame. When the rolling frame time runs
+//! over budget the glow multiplier steps down; once frames are comfortably
+//! back under budget for a while it steps back up. Separate thresholds and
+//! hold times keep it from oscillating.
+
+/// Smoothing factor for the rolling frame time.
+const EMA: f64 = 0.1;
+/// Step down when frames run this far over budget.
+const OVER: f64 = 1.2;
+/// Step up only when frames are this far under the over threshold.
+const UNDER: f64 = 1.05;
+/// Minimum time between step-downs and before a step-up (ms).
+const DOWN_HOLD_MS: f64 = 500.0;
+const UP_HOLD_MS: f64 = 2_000.0;
+/// Glow multiplier change per step.
+const STEP: f32 = 0.25;
+/// Frame gaps longer than this are stalls, not load (ms).
+const MAX_INTERVAL_MS: f64 = 250.0;
+
+pub struct AdaptiveGlow {
+    last: Option<f64>,
+    rolling_ms: f64,
+    scale: f32,
+    last_change: f64,
+}
+
+impl Default for AdaptiveGlow {
+    fn default() -> Self {
+        Self {
+            last: None,
+            rolling_ms: 0.0,
+            scale: 1.0,
+            last_change: 0.0,
+        }
+    }
+}
+
+impl AdaptiveGlow {
+    /// Record a drawn frame at `now` (ms) against `budget_ms` and return the
+    /// glow multiplier to use.
+    pub fn frame(&mut self, now: f64, budget_ms: f64) -> f32 {
+        let Some(last) = self.last.replace(now) else {
+            return self.scale;
+        };
+        let dt = now - last;
+        if dt <= 0.0 || dt > MAX_INTERVAL_MS {
+            return self.scale;
+        }
+        self.rolling_ms = if self.rolling_ms == 0.0 {
+            dt
+        } else {
+            self.rolling_ms * (1.0 - EMA) + dt * EMA
+        };
+
+        let since_change = now - self.last_change;
+        if self.rolling_ms > budget_ms * OVER && since_change > DOWN_HOLD_MS && self.scale > 0.0 {
+            self.scale = (self.scale - STEP).max(0.0);
+            self.last_change = now;
+        } else if self.rolling_ms < budget_ms * UNDER
+            && since_change > UP_HOLD_MS
+            && self.scale < 1.0
+        {
+            self.scale = (self.scale + STEP).min(1.0);
+            self.last_change = now;
+        }
+        self.scale
+    }
+
+    /// Glow is currently being reduced.
+    pub fn is_reducing(&self) -> bool {
+        self.scale < 1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUDGET_MS: f64 = 1000.0 / 60.0;
+
+    /// Feed frames `interval_ms` apart from `start` to `end` and return the
+    /// multiplier after each one.
+    fn run(glow: &mut AdaptiveGlow, start: f64, end: f64, interval_ms: f64) -> Vec<(f64, f32)> {
+        let mut out = Vec::new();
+        let mut now = start;
+        while now <= end {
+            out.push((now, glow.frame(now, BUDGET_MS)));
+            now += interval_ms;
+        }
+        out
+    }
+
+    #[test]
+    fn steps_down_when_over_budget() {
+        let mut glow = AdaptiveGlow::default();
+        let frames = run(&mut glow, 1_000.0, 1_400.0, 30.0);
+        assert_eq!(frames.last().unwrap().1, 1.0 - STEP);
+        assert!(glow.is_reducing());
+    }
+
+    #[test]
+    fn holds_between_step_downs() {
+        let mut glow = AdaptiveGlow::default();
+        let frames = run(&mut glow, 1_000.0, 1_400.0, 30.0);
+        let (first, _) = *frames.iter().find(|(_, s)| *s < 1.0).unwrap();
+        let frames = run(&mut glow, 1_430.0, first + DOWN_HOLD_MS, 30.0);
+        assert!(frames.iter().all(|(_, s)| *s == 1.0 - STEP));
+        let frames = run(
+            &mut glow,
+            first + DOWN_HOLD_MS + 30.0,
+            first + 2.0 * DOWN_HOLD_MS,
+            30.0,
+        );
+        assert_eq!(frames.last().unwrap().1, 1.0 - 2.0 * STEP);
+    }
+
+    #[test]
+    fn steps_up_only_after_the_up_hold() {
+        let mut glow = AdaptiveGlow::default();
+        run(&mut glow, 1_000.0, 1_400.0, 30.0);
+        assert_eq!(glow.scale, 1.0 - STEP);
+        let changed = glow.last_change;
+        // Well under budget: nothing changes until the up hold has passed.
+        let frames = run(&mut glow, 1_410.0, changed + UP_HOLD_MS, 10.0);
+        assert!(frames.iter().all(|(_, s)| *s == 1.0 - STEP));
+        let frames = run(
+            &mut glow,
+            changed + UP_HOLD_MS + 1.0,
+            changed + UP_HOLD_MS + 100.0,
+            10.0,
+        );
+        assert_eq!(frames.last().unwrap().1, 1.0);
+        assert!(!glow.is_reducing());
+    }
+
+    #[test]
+    fn ignores_stalls() {
+        let mut glow = AdaptiveGlow::default();
+        run(&mut glow, 1_000.0, 1_200.0, BUDGET_MS);
+        let rolling = glow.rolling_ms;
+        assert_eq!(glow.frame(1_200.0 + MAX_INTERVAL_MS * 4.0, BUDGET_MS), 1.0);
+        assert_eq!(glow.rolling_ms, rolling);
+    }
+}
@@ -0,0 +1,31 @@
+//! Page URL query-string access.
+
+use std::collections::HashMap;
+
+use wasm_bindgen::JsCast;
+use web_sys::UrlSearchParams;
+
+/// Look up a query-string parameter on the current page URL.
+pub fn param(name: &str) -> Option<String> {
+    let search = web_sys::window()?.location().search().ok()?;
+    UrlSearchParams::new_with_str(&search).ok()?.get(name)
+}
+
+/// Every query-string parameter on the current page URL. A repeated key
+/// keeps its last value.
+pub fn all() -> HashMap<String, String> {
+    let mut params = HashMap::new();
+    let Some(search) = web_sys::window().and_then(|w| w.location().search().ok()) else {
+        return params;
+    };
+    let Ok(parsed) = UrlSearchParams::new_with_str(&search) else {
+        return params;
+    };
+    for entry in parsed.entries().into_iter().flatten() {
+        let pair: js_sys::Array = entry.unchecked_into();
+        if let (Some(k), Some(v)) = (pair.get(0).as_string(), pair.get(1).as_string()) {
+            params.insert(k, v);
+        }
+    }
+    params
+}
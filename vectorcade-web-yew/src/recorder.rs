@@ -0,0 +1,95 @@
+//! Gameplay clip capture to WebM via `MediaRecorder`.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    Blob, BlobEvent, BlobPropertyBag, HtmlAnchorElement, HtmlCanvasElement, MediaRecorder,
+    MediaRecorderOptions, Url,
+};
+
+use crate::logging;
+
+const MIME_TYPE: &str = "video/webm";
+
+/// Whether this browser can record the canvas.
+pub fn supported() -> bool {
+    web_sys::window().is_some_and(|w| {
+        js_sys::Reflect::has(&w, &JsValue::from_str("MediaRecorder")).unwrap_or(false)
+    })
+}
+
+/// An in-progress canvas recording. Stopping it downloads the clip.
+pub struct Recording {
+    recorder: MediaRecorder,
+}
+
+impl Recording {
+    /// Start recording `canvas`; `on_done` runs after the download is offered.
+    pub fn start(
+        canvas: &HtmlCanvasElement,
+        on_done: impl FnOnce() + 'static,
+    ) -> Result<Self, JsValue> {
+        let stream = canvas.capture_stream()?;
+        let options = MediaRecorderOptions::new();
+        options.set_mime_type(MIME_TYPE);
+        let recorder =
+            MediaRecorder::new_with_media_stream_and_media_recorder_options(&stream, &options)?;
+
+        let chunks: Rc<RefCell<Vec<Blob>>> = Rc::default();
+        let on_data = {
+            let chunks = chunks.clone();
+            Closure::<dyn FnMut(BlobEvent)>::new(move |e: BlobEvent| {
+                if let Some(blob) = e.data() {
+                    chunks.borrow_mut().push(blob);
+                }
+            })
+        };
+        let mut on_done = Some(on_done);
+        let on_stop = Closure::<dyn FnMut()>::new(move || {
+            if let Err(e) = download(&chunks.borrow()) {
+                logging::error(&format!("clip download failed: {:?}", e));
+            }
+            if let Some(done) = on_done.take() {
+                done();
+            }
+        });
+        recorder.set_ondataavailable(Some(on_data.as_ref().unchecked_ref()));
+        recorder.set_onstop(Some(on_stop.as_ref().unchecked_ref()));
+        on_data.forget();
+        on_stop.forget();
+
+        recorder.start()?;
+        logging::info("recording started");
+        Ok(Self { recorder })
+    }
+
+    pub fn stop(&self) {
+        if let Err(e) = self.recorder.stop() {
+            logging::warn(&format!("recorder stop failed: {:?}", e));
+        }
+    }
+}
+
+/// Join the recorded chunks into one WebM blob and offer it as a download.
+fn download(chunks: &[Blob]) -> Result<(), JsValue> {
+    let parts = js_sys::Array::new();
+    for chunk in chunks {
+        parts.push(chunk);
+    }
+    let props = BlobPropertyBag::new();
+    props.set_type(MIME_TYPE);
+    let blob = Blob::new_with_blob_sequence_and_options(&parts, &props)?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+
+    let document = web_sys::window()
+        .and_then(|w| w.document())
+        .ok_or_else(|| JsValue::from_str("no document"))?;
+    let link: HtmlAnchorElement = document.create_element("a")?.dyn_into()?;
+    link.set_href(&url);
+    link.set_download("vectorcade.webm");
+    link.click();
+    Url::revoke_object_url(&url)
+}
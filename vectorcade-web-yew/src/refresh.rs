@@ -0,0 +1,103 @@
+//! Display refresh-rate measurement.
+//!
+//! The fixed timestep runs at 60 Hz, but requestAnimationFrame fires at the
+//! display's rate. The meter averages the first frames to estimate that rate
+//! and starts over if the rolling interval drifts, e.g. when the window moves
+//! to a monitor with a different refresh rate.
+//!
+//! `FrameLimiter` optionally draws fewer frames than the display offers.
+
+/// Frames averaged for a measurement.
+const SAMPLE_FRAMES: usize = 60;
+/// Relative change in the rolling interval that triggers a re-measure.
+const DRIFT: f64 = 0.15;
+/// Intervals longer than this are stalls (tab hidden, debugger), not vsync.
+const MAX_INTERVAL_MS: f64 = 250.0;
+/// Above this rate, rendering between fixed steps is worth interpolating.
+const INTERPOLATE_ABOVE_HZ: f64 = 75.0;
+/// Timestamp jitter tolerated before a capped frame counts as early (ms).
+const LIMIT_SLACK_MS: f64 = 2.0;
+
+#[derive(Default)]
+pub struct RefreshMeter {
+    last: Option<f64>,
+    samples: Vec<f64>,
+    interval_ms: Option<f64>,
+    rolling_ms: f64,
+}
+
+impl RefreshMeter {
+    /// Record a frame at `now` (ms). Returns true when a new measurement lands.
+    pub fn frame(&mut self, now: f64) -> bool {
+        let Some(last) = self.last.replace(now) else {
+            return false;
+        };
+        let dt = now - last;
+        if dt <= 0.0 || dt > MAX_INTERVAL_MS {
+            return false;
+        }
+
+        match self.interval_ms {
+            None => {
+                self.samples.push(dt);
+                if self.samples.len() < SAMPLE_FRAMES {
+                    return false;
+                }
+                let avg = self.samples.iter().sum::<f64>() / self.samples.len() as f64;
+                self.samples.clear();
+                self.interval_ms = Some(avg);
+                self.rolling_ms = avg;
+                true
+            }
+            Some(interval) => {
+                self.rolling_ms = self.rolling_ms * 0.95 + dt * 0.05;
+                if (self.rolling_ms - interval).abs() / interval > DRIFT {
+                    self.interval_ms = None;
+                }
+                false
+            }
+        }
+    }
+
+    /// Measured refresh rate in Hz, once available.
+    pub fn hz(&self) -> Option<f64> {
+        self.interval_ms.map(|ms| 1000.0 / ms)
+    }
+
+    /// Whether the display is fast enough that interpolating between fixed
+    /// steps would be visible (skipped around 60 Hz, enabled at 120 Hz+).
+    pub fn wants_interpolation(&self) -> bool {
+        self.hz().is_some_and(|hz| hz > INTERPOLATE_ABOVE_HZ)
+    }
+}
+
+/// Skips frames to hold rendering at or below a target rate.
+///
+/// Safe because the fixed-step accumulator already decouples simulation
+/// from how often frames are drawn.
+#[derive(Default)]
+pub struct FrameLimiter {
+    next_due: f64,
+}
+
+impl FrameLimiter {
+    /// Whether a frame should be drawn at `now` (ms) under `max_hz`
+    /// (`None` = uncapped).
+    pub fn due(&mut self, now: f64, max_hz: Option<f64>) -> bool {
+        let Some(hz) = max_hz.filter(|hz| *hz > 0.0) else {
+            return true;
+        };
+        if now < self.next_due - LIMIT_SLACK_MS {
+            return false;
+        }
+        let interval = 1000.0 / hz;
+        // Step the schedule rather than restarting it from `now`, so the
+        // average rate holds; resync after a stall.
+        self.next_due = if now - self.next_due > interval {
+            now + interval
+        } else {
+            self.next_due + interval
+        };
+        true
+    }
+}
@@ -0,0 +1,116 @@
+//! Arcade-style hold-to-repeat for discrete moves: one step on press, then
+//! repeats after a delay, optionally speeding up as the hold goes on.
+
+/// How a held key repeats. All times in ms.
+#[derive(Clone, Copy)]
+pub struct RepeatRate {
+    /// Hold time before the first repeat.
+    pub delay_ms: f64,
+    /// First repeat interval; each repeat shortens it by `step_ms`.
+    pub interval_ms: f64,
+    pub step_ms: f64,
+    /// Fastest repeat interval.
+    pub min_ms: f64,
+}
+
+impl RepeatRate {
+    /// Repeats every `interval_ms` after `delay_ms`, without speeding up.
+    pub const fn steady(delay_ms: f64, interval_ms: f64) -> Self {
+        Self {
+            delay_ms,
+            interval_ms,
+            step_ms: 0.0,
+            min_ms: interval_ms,
+        }
+    }
+
+    /// Interval after the `repeats`th repeat.
+    fn interval(&self, repeats: u32) -> f64 {
+        (self.interval_ms - self.step_ms * repeats as f64)
+            .max(self.min_ms)
+            .max(1.0)
+    }
+}
+
+/// Repeat timer for one held key.
+pub struct HoldRepeat {
+    rate: RepeatRate,
+    next_at: Option<f64>,
+    repeats: u32,
+}
+
+impl HoldRepeat {
+    pub const fn new(rate: RepeatRate) -> Self {
+        Self {
+            rate,
+            next_at: None,
+            repeats: 0,
+        }
+    }
+
+    /// Start a hold at `now` (ms), restarting one already running.
+    pub fn press(&mut self, now: f64) {
+        self.next_at = Some(now + self.rate.delay_ms);
+        self.repeats = 0;
+    }
+
+    pub fn release(&mut self) {
+        self.next_at = None;
+    }
+
+    /// Repeat steps that fell due by `now` (ms).
+    pub fn poll(&mut self, now: f64) -> u32 {
+        let mut steps = 0;
+        while let Some(at) = self.next_at.filter(|at| now >= *at) {
+            steps += 1;
+            self.repeats += 1;
+            self.next_at = Some(at + self.rate.interval(self.repeats));
+        }
+        steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steady_repeats_after_the_delay() {
+        let mut hold = HoldRepeat::new(RepeatRate::steady(400.0, 100.0));
+        hold.press(1_000.0);
+        assert_eq!(hold.poll(1_399.0), 0);
+        assert_eq!(hold.poll(1_400.0), 1);
+        assert_eq!(hold.poll(1_450.0), 0);
+        assert_eq!(hold.poll(1_700.0), 3);
+    }
+
+    #[test]
+    fn accelerates_down_to_the_minimum() {
+        let rate = RepeatRate {
+            delay_ms: 100.0,
+            interval_ms: 50.0,
+            step_ms: 20.0,
+            min_ms: 20.0,
+        };
+        let mut hold = HoldRepeat::new(rate);
+        hold.press(0.0);
+        // Due at 100, 130, 150, 170, ...
+        assert_eq!(hold.poll(100.0), 1);
+        assert_eq!(hold.poll(129.0), 0);
+        assert_eq!(hold.poll(130.0), 1);
+        assert_eq!(hold.poll(170.0), 2);
+    }
+
+    #[test]
+    fn release_stops_and_press_restarts() {
+        let mut hold = HoldRepeat::new(RepeatRate::steady(400.0, 100.0));
+        hold.press(0.0);
+        hold.release();
+        assert_eq!(hold.poll(1_000.0), 0);
+        hold.press(1_000.0);
+        assert_eq!(hold.poll(1_300.0), 0);
+        hold.press(1_300.0);
+        assert_eq!(hold.poll(1_600.0), 0);
+        assert_eq!(hold.poll(1_700.0), 1);
+    }
+}
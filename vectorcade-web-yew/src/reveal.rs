@@ -0,0 +1,42 @@
+//! Beam-drawn reveal of a game's first frames after switching to it
+//! (Settings → Display → Title reveal).
+//!
+//! Every path grows from its start to its full length over the reveal
+//! time, so title screens look traced by the beam. Skipped under reduced
+//! motion.
+
+use vectorcade_shared::draw::DrawCmd;
+
+use crate::{display_list, view};
+
+#[derive(Default)]
+pub struct Reveal {
+    started: Option<f64>,
+}
+
+impl Reveal {
+    /// Begin a reveal at `now` (ms).
+    pub fn start(&mut self, now: f64) {
+        if !view::prefers_reduced_motion() {
+            self.started = Some(now);
+        }
+    }
+
+    /// Cut the game's `cmds` to the reveal's progress at `now`, taking
+    /// `duration_s` in all (0 turns reveals off).
+    pub fn apply(&mut self, now: f64, duration_s: f32, cmds: &mut Vec<DrawCmd>) {
+        let Some(started) = self.started else {
+            return;
+        };
+        let t = if duration_s > 0.0 {
+            ((now - started) / (duration_s as f64 * 1000.0)) as f32
+        } else {
+            1.0
+        };
+        if t >= 1.0 {
+            self.started = None;
+            return;
+        }
+        display_list::reveal(cmds, t);
+    }
+}
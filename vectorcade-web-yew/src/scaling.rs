@@ -0,0 +1,209 @@
+//! Playfield scaling modes.
+//!
+//! The renderer fits the NDC square to the shorter canvas axis (Fit). The
+//! other modes scale the game's draw list about the origin on top of that, and
+//! pointer input is divided by the same factor so it stays aligned.
+
+use glam::Vec2;
+use vectorcade_shared::draw::DrawCmd;
+use vectorcade_shared::game::ScreenInfo;
+use vectorcade_shared::Rgba;
+
+use crate::overlay;
+use crate::settings::ScaleMode;
+
+/// Logical playfield size for integer scaling, in device pixels.
+///
+/// Fixed until games can declare a logical resolution.
+pub const LOGICAL_PX: f32 = 256.0;
+
+/// Playfield size, in device pixels, at which relative text keeps its
+/// authored `size_px`.
+pub const REFERENCE_TEXT_PX: f32 = 600.0;
+
+/// Backing-store size, in device pixels, for a canvas box of `css_w` ×
+/// `css_h` CSS pixels at `dpr`.
+///
+/// Rounded, not truncated: at fractional ratios (1.25, 1.5, 2.625) the box
+/// covers a fractional number of device pixels, which the browser snaps to
+/// the nearest one when it lays the canvas out. Truncating left the backing
+/// store a pixel short, so it was upscaled (blurry) with a 1px seam. The
+/// canvas's CSS box is then set to `backing / dpr`, so box and backing
+/// store agree exactly instead of relying on the browser's snapping.
+pub fn backing_size(css_w: f64, css_h: f64, dpr: f64) -> (u32, u32) {
+    let px = |css: f64| (css * dpr).round().max(1.0) as u32;
+    (px(css_w), px(css_h))
+}
+
+/// Factor for game text sizes so text keeps its size relative to the
+/// playfield (`playfield_scale` from `scale`) rather than to the screen.
+pub fn text_scale(screen: &ScreenInfo, playfield_scale: f32) -> f32 {
+    let short = screen.width_px.min(screen.height_px).max(1) as f32;
+    short * playfield_scale / (REFERENCE_TEXT_PX * screen.dpi_scale.max(0.1))
+}
+
+/// Factor applied to game NDC for `mode` on `screen`.
+pub fn scale(mode: ScaleMode, screen: &ScreenInfo) -> f32 {
+    let short = screen.width_px.min(screen.height_px).max(1) as f32;
+    let long = screen.width_px.max(screen.height_px).max(1) as f32;
+    match mode {
+        ScaleMode::Fit => 1.0,
+        // Cover the long axis too, cropping the short one.
+        ScaleMode::Fill => long / short,
+        // Largest whole multiple of the logical size that fits.
+        ScaleMode::Integer => {
+            let multiple = (short / LOGICAL_PX).floor();
+            if multiple < 1.0 {
+                1.0
+            } else {
+                multiple * LOGICAL_PX / short
+            }
+        }
+    }
+}
+
+/// Paint the canvas outside a playfield scaled by `playfield_scale` in
+/// `color`.
+///
+/// Each bar is one line as thick as the bar, running past the canvas edges
+/// so its caps are never seen.
+pub fn letterbox_bars(
+    screen: &ScreenInfo,
+    playfield_scale: f32,
+    color: Rgba,
+    out: &mut Vec<DrawCmd>,
+) {
+    let short = screen.width_px.min(screen.height_px).max(1) as f32;
+    let half = Vec2::new(screen.width_px as f32, screen.height_px as f32) / short;
+    let edge = playfield_scale;
+    let px_per_ndc = 1.0 / overlay::ndc_per_px(screen);
+    for (axis, other) in [(Vec2::X, Vec2::Y), (Vec2::Y, Vec2::X)] {
+        let extent = half.dot(axis);
+        if extent <= edge {
+            continue;
+        }
+        let mid = (edge + extent) * 0.5;
+        // A pixel of overlap so no seam shows against the playfield.
+        let width_px = (extent - edge) * px_per_ndc + 1.0;
+        let reach = other * (half.dot(other) + 1.0);
+        for side in [-1.0, 1.0] {
+            let center = axis * mid * side;
+            overlay::line(out, center - reach, center + reach, color, width_px);
+        }
+    }
+}
+
+/// Maps between game NDC and canvas device pixels: the NDC square fitted
+/// to the shorter axis and centered, Y up, scaled by the playfield factor.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ViewTransform {
+    center: Vec2,
+    /// Device pixels per game NDC unit.
+    px_per_ndc: f32,
+}
+
+impl ViewTransform {
+    pub fn new(screen: &ScreenInfo, playfield_scale: f32) -> Self {
+        let size = Vec2::new(screen.width_px as f32, screen.height_px as f32);
+        Self {
+            center: size * 0.5,
+            px_per_ndc: playfield_scale / overlay::ndc_per_px(screen),
+        }
+    }
+
+    /// Game NDC to device pixels (origin top-left, Y down).
+    pub fn to_px(self, p: Vec2) -> Vec2 {
+        self.center + Vec2::new(p.x, -p.y) * self.px_per_ndc
+    }
+
+    /// Device pixels to game NDC.
+    pub fn from_px(self, px: Vec2) -> Vec2 {
+        let d = (px - self.center) / self.px_per_ndc;
+        Vec2::new(d.x, -d.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orientation::Orientation;
+
+    fn screen(width_px: u32, height_px: u32) -> ScreenInfo {
+        ScreenInfo {
+            width_px,
+            height_px,
+            dpi_scale: 1.0,
+        }
+    }
+
+    fn close(a: Vec2, b: Vec2) -> bool {
+        (a - b).abs().max_element() < 1e-4
+    }
+
+    #[test]
+    fn view_center_is_canvas_center() {
+        let view = ViewTransform::new(&screen(1600, 900), 1.0);
+        assert_eq!(view.to_px(Vec2::ZERO), Vec2::new(800.0, 450.0));
+        assert_eq!(view.from_px(Vec2::new(800.0, 450.0)), Vec2::ZERO);
+    }
+
+    #[test]
+    fn view_corner_follows_short_axis_and_scale() {
+        // Short axis 900: NDC (1, 1) is 450 px right of and above center.
+        let view = ViewTransform::new(&screen(1600, 900), 1.0);
+        assert_eq!(view.to_px(Vec2::ONE), Vec2::new(1250.0, 0.0));
+        assert_eq!(view.to_px(Vec2::NEG_ONE), Vec2::new(350.0, 900.0));
+        // Tall canvas, playfield scaled by 0.5.
+        let view = ViewTransform::new(&screen(600, 1000), 0.5);
+        assert_eq!(view.to_px(Vec2::ONE), Vec2::new(450.0, 350.0));
+    }
+
+    #[test]
+    fn view_round_trips() {
+        let points = [
+            Vec2::new(0.3, -0.7),
+            Vec2::new(-1.0, 1.0),
+            Vec2::new(1.7, 0.2),
+        ];
+        for (size, scale) in [((1600, 900), 1.0), ((640, 1136), 1.4), ((333, 333), 0.8)] {
+            let view = ViewTransform::new(&screen(size.0, size.1), scale);
+            for p in points {
+                assert!(close(view.from_px(view.to_px(p)), p));
+            }
+        }
+    }
+
+    #[test]
+    fn pointer_round_trips_through_orientation() {
+        let view = ViewTransform::new(&screen(1280, 720), 1.25);
+        let p = Vec2::new(0.4, -0.6);
+        for turns in 0..4 {
+            for (flip_x, flip_y) in [(false, false), (true, false), (false, true), (true, true)] {
+                let orientation = Orientation {
+                    turns,
+                    flip_x,
+                    flip_y,
+                };
+                let px = view.to_px(orientation.apply(p));
+                assert!(close(orientation.unapply(view.from_px(px)), p));
+            }
+        }
+    }
+
+    #[test]
+    fn backing_size_rounds_fractional_ratios() {
+        // 801 × 1.5 = 1201.5 and 601 × 1.5 = 901.5 round up.
+        assert_eq!(backing_size(801.0, 601.0, 1.5), (1202, 902));
+        // 411 × 2.625 = 1078.875, 731 × 2.625 = 1918.875 (a common phone).
+        assert_eq!(backing_size(411.0, 731.0, 2.625), (1079, 1919));
+        // 100.2 × 2.625 = 263.025 rounds down.
+        assert_eq!(backing_size(100.2, 100.2, 2.625), (263, 263));
+    }
+
+    #[test]
+    fn backing_size_is_never_zero() {
+        assert_eq!(backing_size(0.0, 0.0, 1.0), (1, 1));
+        assert_eq!(backing_size(0.1, 0.3, 1.5), (1, 1));
+        assert_eq!(backing_size(0.2, 0.2, 2.625), (1, 1));
+    }
+}
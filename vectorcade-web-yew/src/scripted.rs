@@ -0,0 +1,353 @@
+//! Games defined as data and interpreted behind the `Game` trait.
+//!
+//! A definition is JSON: shapes that move, spin and wrap around the
+//! playfield (one of them optionally steered with the arrow keys) plus fixed
+//! text. Definitions bundled from `games/` and any added at runtime with the
+//! `load_game(json)` export are registered after `all_games()`.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use glam::Vec2;
+use serde::Deserialize;
+use vectorcade_games::all_games;
+use vectorcade_shared::draw::{DrawCmd, Stroke};
+use vectorcade_shared::font::FontStyleId;
+use vectorcade_shared::game::{Game, GameCtx, GameMeta};
+use vectorcade_shared::input::Key;
+use vectorcade_shared::Rgba;
+use wasm_bindgen::prelude::*;
+
+use crate::{debug_draw, logging, thumbnails, GAME_STATE};
+
+/// Definitions shipped with the shell.
+const BUNDLED: &[&str] = &[include_str!("../games/drift.json")];
+
+thread_local! {
+    static DEFINITIONS: RefCell<Vec<Rc<Definition>>> = RefCell::new(
+        BUNDLED
+            .iter()
+            .filter_map(|json| match parse(json) {
+                Ok(spec) => Some(Rc::new(define(spec, None))),
+                Err(e) => {
+                    logging::error(&format!("bundled game definition: {}", e));
+                    None
+                }
+            })
+            .collect(),
+    );
+}
+
+/// A parsed definition. The id and name are leaked on load: `GameMeta`
+/// wants `&'static str`, and definitions live for the whole session.
+/// Reloading an id reuses the strings leaked the first time.
+struct Definition {
+    id: &'static str,
+    name: &'static str,
+    spec: Spec,
+}
+
+#[derive(Deserialize)]
+struct Spec {
+    id: String,
+    name: String,
+    #[serde(default)]
+    background: [f32; 3],
+    #[serde(default)]
+    entities: Vec<EntityDef>,
+    #[serde(default)]
+    labels: Vec<LabelDef>,
+}
+
+/// A shape and how it moves. Shape points are in units of `scale` around
+/// the entity's position; positions and velocities are NDC (per second).
+#[derive(Deserialize)]
+#[serde(default)]
+struct EntityDef {
+    shape: Vec<[f32; 2]>,
+    closed: bool,
+    pos: [f32; 2],
+    vel: [f32; 2],
+    angle: f32,
+    /// Radians per second.
+    spin: f32,
+    scale: f32,
+    color: [f32; 3],
+    width_px: f32,
+    glow: f32,
+    /// Wrap around the playfield edges instead of leaving it.
+    wrap: bool,
+    /// Arrow keys move this entity at this speed; 0 leaves it unsteered.
+    player_speed: f32,
+}
+
+impl Default for EntityDef {
+    fn default() -> Self {
+        Self {
+            shape: Vec::new(),
+            closed: true,
+            pos: [0.0; 2],
+            vel: [0.0; 2],
+            angle: 0.0,
+            spin: 0.0,
+            scale: 0.1,
+            color: [1.0; 3],
+            width_px: 2.0,
+            glow: 1.0,
+            wrap: true,
+            player_speed: 0.0,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+struct LabelDef {
+    text: String,
+    pos: [f32; 2],
+    size_px: f32,
+    color: [f32; 3],
+}
+
+impl Default for LabelDef {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            pos: [0.0; 2],
+            size_px: 16.0,
+            color: [1.0; 3],
+        }
+    }
+}
+
+fn parse(json: &str) -> Result<Spec, serde_json::Error> {
+    serde_json::from_str(json)
+}
+
+/// A definition for `spec`, taking the id and name from `prev` (the
+/// definition it replaces) where they match.
+fn define(spec: Spec, prev: Option<&Definition>) -> Definition {
+    let leak = |s: &str| -> &'static str { Box::leak(s.to_owned().into_boxed_str()) };
+    let id = match prev {
+        Some(prev) if prev.id == spec.id => prev.id,
+        _ => leak(&spec.id),
+    };
+    let name = match prev {
+        Some(prev) if prev.name == spec.name => prev.name,
+        _ => leak(&spec.name),
+    };
+    Definition { id, name, spec }
+}
+
+/// Move `pos` back onto the playfield across the opposite edge.
+fn wrap(pos: Vec2) -> Vec2 {
+    (pos + Vec2::ONE).rem_euclid(Vec2::splat(2.0)) - Vec2::ONE
+}
+
+/// Moving state of one entity.
+struct Entity {
+    pos: Vec2,
+    vel: Vec2,
+    angle: f32,
+}
+
+/// A `Game` that plays a `Definition`.
+struct ScriptedGame {
+    def: Rc<Definition>,
+    entities: Vec<Entity>,
+}
+
+impl ScriptedGame {
+    fn new(def: Rc<Definition>) -> Self {
+        let mut game = Self {
+            def,
+            entities: Vec::new(),
+        };
+        game.restart();
+        game
+    }
+
+    fn restart(&mut self) {
+        self.entities = self
+            .def
+            .spec
+            .entities
+            .iter()
+            .map(|e| Entity {
+                pos: Vec2::from(e.pos),
+                vel: Vec2::from(e.vel),
+                angle: e.angle,
+            })
+            .collect();
+    }
+}
+
+impl Game for ScriptedGame {
+    fn metadata(&self) -> GameMeta {
+        GameMeta {
+            id: self.def.id,
+            name: self.def.name,
+            ..GameMeta::default()
+        }
+    }
+
+    fn reset(&mut self, _ctx: &mut GameCtx) {
+        self.restart();
+    }
+
+    fn update(&mut self, ctx: &mut GameCtx, dt: f32) {
+        let held = |k: Key| if ctx.input.key(k).is_down { 1.0 } else { 0.0 };
+        let steer = Vec2::new(
+            held(Key::Right) - held(Key::Left),
+            held(Key::Up) - held(Key::Down),
+        )
+        .normalize_or_zero();
+        for (def, e) in self.def.spec.entities.iter().zip(&mut self.entities) {
+            if def.player_speed > 0.0 {
+                e.vel = steer * def.player_speed;
+            }
+            e.pos += e.vel * dt;
+            e.angle += def.spin * dt;
+            if def.wrap {
+                e.pos = wrap(e.pos);
+            }
+        }
+    }
+
+    fn render(&mut self, _ctx: &mut GameCtx, out: &mut Vec<DrawCmd>) {
+        let [r, g, b] = self.def.spec.background;
+        out.push(DrawCmd::Clear {
+            color: Rgba(r, g, b, 1.0),
+        });
+        for (def, e) in self.def.spec.entities.iter().zip(&self.entities) {
+            let rot = Vec2::from_angle(e.angle);
+            let pts = def
+                .shape
+                .iter()
+                .map(|&p| e.pos + rot.rotate(Vec2::from(p) * def.scale))
+                .collect();
+            let reach = def
+                .shape
+                .iter()
+                .map(|&p| Vec2::from(p).length())
+                .fold(0.0, f32::max);
+            debug_draw::circle(e.pos, reach * def.scale);
+            let [r, g, b] = def.color;
+            out.push(DrawCmd::Polyline {
+                pts,
+                closed: def.closed,
+                stroke: Stroke {
+                    color: Rgba(r, g, b, 1.0),
+                    width_px: def.width_px,
+                    glow: def.glow,
+                },
+            });
+        }
+        for label in &self.def.spec.labels {
+            let [r, g, b] = label.color;
+            out.push(DrawCmd::Text {
+                pos: Vec2::from(label.pos),
+                text: label.text.clone(),
+                size_px: label.size_px,
+                color: Rgba(r, g, b, 1.0),
+                style: FontStyleId::ATARI,
+            });
+        }
+    }
+}
+
+/// Every registered game: `all_games()` followed by the data-defined ones.
+pub fn registry() -> Vec<Box<dyn Game>> {
+    // Data-defined games share their definition through an `Rc`, so the
+    // list drops the `Send` bound `all_games()` carries.
+    let mut games: Vec<Box<dyn Game>> = all_games()
+        .into_iter()
+        .map(|game| game as Box<dyn Game>)
+        .collect();
+    DEFINITIONS.with(|defs| {
+        games.extend(
+            defs.borrow()
+                .iter()
+                .map(|def| Box::new(ScriptedGame::new(def.clone())) as Box<dyn Game>),
+        );
+    });
+    games
+}
+
+/// Register a game definition (JSON) and add it to the game list, replacing
+/// a loaded definition with the same id. Ids of `all_games()` are refused.
+#[wasm_bindgen]
+pub fn load_game(json: &str) -> Result<(), JsValue> {
+    let spec = parse(json).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    if all_games().iter().any(|g| g.metadata().id == spec.id) {
+        return Err(JsValue::from_str(&format!(
+            "game id {:?} belongs to a built-in game",
+            spec.id
+        )));
+    }
+    let def = DEFINITIONS.with(|defs| {
+        let mut defs = defs.borrow_mut();
+        let idx = defs.iter().position(|d| d.id == spec.id);
+        let def = Rc::new(define(spec, idx.map(|i| &*defs[i])));
+        match idx {
+            Some(i) => defs[i] = def.clone(),
+            None => defs.push(def.clone()),
+        }
+        def
+    });
+    logging::info(&format!("loaded game definition {:?}", def.id));
+    thumbnails::forget(&def.id);
+    GAME_STATE.with(|state| {
+        state
+            .borrow_mut()
+            .register_game(Box::new(ScriptedGame::new(def)))
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drift() -> Spec {
+        parse(BUNDLED[0]).unwrap()
+    }
+
+    #[test]
+    fn parses_bundled_drift() {
+        let spec = drift();
+        assert_eq!(spec.id, "drift");
+        assert_eq!(spec.name, "Drift");
+        assert_eq!(spec.entities.len(), 3);
+        assert_eq!(spec.entities[0].player_speed, 0.8);
+        assert_eq!(spec.entities[1].player_speed, 0.0);
+        // Unset fields take the defaults.
+        assert!(spec.entities.iter().all(|e| e.wrap && e.closed));
+        assert_eq!(spec.entities[0].pos, [0.0; 2]);
+        assert_eq!(spec.background, [0.0; 3]);
+        assert_eq!(spec.labels[0].text, "DRIFT");
+        assert_eq!(spec.labels[0].color, [1.0; 3]);
+    }
+
+    #[test]
+    fn redefining_reuses_leaked_strings() {
+        let first = define(drift(), None);
+        let again = define(drift(), Some(&first));
+        assert!(std::ptr::eq(first.id, again.id));
+        assert!(std::ptr::eq(first.name, again.name));
+        let mut renamed = drift();
+        renamed.name = "Drift II".into();
+        let renamed = define(renamed, Some(&first));
+        assert!(std::ptr::eq(first.id, renamed.id));
+        assert_eq!(renamed.name, "Drift II");
+    }
+
+    #[test]
+    fn wraps_across_opposite_edges() {
+        assert_eq!(wrap(Vec2::new(0.5, -0.25)), Vec2::new(0.5, -0.25));
+        let p = wrap(Vec2::new(1.25, -1.5));
+        assert!((p - Vec2::new(-0.75, 0.5)).length() < 1e-6);
+        let p = wrap(Vec2::new(-3.5, 3.0));
+        assert!((p - Vec2::new(0.5, -1.0)).length() < 1e-6);
+    }
+}
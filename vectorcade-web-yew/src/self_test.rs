@@ -0,0 +1,89 @@
+//! Coin-op style power-on self-test shown before the first game.
+//!
+//! Purely cosmetic: the checks always pass. It gives the audio context and
+//! renderer a moment to settle, and any key skips it. Off unless Settings →
+//! Cabinet gives it a duration.
+
+use glam::Vec2;
+use vectorcade_shared::draw::DrawCmd;
+use vectorcade_shared::game::ScreenInfo;
+use vectorcade_shared::Rgba;
+
+use crate::overlay;
+
+/// Checks listed on the screen; each reports OK once the bar passes it.
+const CHECKS: [&str; 5] = ["RAM", "ROM", "VECTOR GENERATOR", "MATH BOX", "SOUND"];
+const SIZE_PX: f32 = 16.0;
+
+#[derive(Default)]
+pub struct SelfTest {
+    start: Option<f64>,
+    done: bool,
+}
+
+impl SelfTest {
+    /// Whether the test still owns the screen at `now` (ms). The first call
+    /// starts the clock; `duration_s` of 0 means the test is off.
+    pub fn running(&mut self, now: f64, duration_s: f32) -> bool {
+        if self.done {
+            return false;
+        }
+        let start = *self.start.get_or_insert(now);
+        if duration_s <= 0.0 || now - start >= duration_s as f64 * 1000.0 {
+            self.done = true;
+        }
+        !self.done
+    }
+
+    /// End the test early; returns false if it wasn't on screen.
+    pub fn skip(&mut self) -> bool {
+        let showing = self.start.is_some() && !self.done;
+        self.done = true;
+        showing
+    }
+
+    /// Draw the test screen at `now` (ms) in place of a game frame.
+    pub fn draw(&self, now: f64, duration_s: f32, screen: &ScreenInfo, out: &mut Vec<DrawCmd>) {
+        let elapsed = self.start.map_or(0.0, |start| now - start);
+        let progress = (elapsed / (duration_s.max(0.1) as f64 * 1000.0)).clamp(0.0, 1.0) as f32;
+        let color = Rgba(0.3, 1.0, 0.6, 1.0);
+        let dim = Rgba(0.3, 1.0, 0.6, 0.4);
+        out.push(DrawCmd::Clear {
+            color: Rgba(0.0, 0.0, 0.0, 1.0),
+        });
+        overlay::text_centered(out, screen, Vec2::new(0.0, 0.6), "SELF TEST", 24.0, color);
+
+        let step = SIZE_PX * overlay::LINE_ADVANCE * overlay::ndc_per_px(screen) * 1.5;
+        for (i, check) in CHECKS.iter().enumerate() {
+            let y = 0.35 - step * i as f32;
+            overlay::text(out, Vec2::new(-0.6, y), check, SIZE_PX, color);
+            // A check passes once the bar is past its share of the run.
+            let passed = progress >= (i + 1) as f32 / CHECKS.len() as f32;
+            let (result, result_color) = if passed { ("OK", color) } else { ("....", dim) };
+            overlay::text(out, Vec2::new(0.4, y), result, SIZE_PX, result_color);
+        }
+
+        let (min, max) = (Vec2::new(-0.6, -0.55), Vec2::new(0.6, -0.5));
+        overlay::rect(out, min, max, color, 1.5);
+        if progress > 0.0 {
+            let mid = (min.y + max.y) * 0.5;
+            let width_px = (max.y - min.y) / overlay::ndc_per_px(screen) - 4.0;
+            let end = min.x + (max.x - min.x) * progress;
+            overlay::line(
+                out,
+                Vec2::new(min.x, mid),
+                Vec2::new(end, mid),
+                color,
+                width_px.max(1.0),
+            );
+        }
+        overlay::text_centered(
+            out,
+            screen,
+            Vec2::new(0.0, -0.75),
+            "PRESS ANY KEY",
+            12.0,
+            dim,
+        );
+    }
+}
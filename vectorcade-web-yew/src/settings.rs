@@ -0,0 +1,455 @@
+//! User-tunable platform settings.
+//!
+//! Settings live in `GameState` and are edited through the settings dialog.
+//! Subsystems read the values they care about each frame. Settings persist
+//! to `localStorage`; fields missing from older saves take their defaults.
+
+use serde::{Deserialize, Serialize};
+
+/// `localStorage` key for saved settings.
+pub const STORAGE_KEY: &str = "vectorcade.settings";
+
+/// Monochrome phosphor emulation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Phosphor {
+    /// Game colors as drawn.
+    #[default]
+    None,
+    /// P1 green, as on early vector monitors.
+    Green,
+    /// P3 amber.
+    Amber,
+}
+
+impl Phosphor {
+    pub const ALL: [Phosphor; 3] = [Phosphor::None, Phosphor::Green, Phosphor::Amber];
+
+    /// Phosphor color at full brightness, or `None` for full color.
+    pub fn hue(self) -> Option<[f32; 3]> {
+        match self {
+            Phosphor::None => None,
+            Phosphor::Green => Some([0.25, 1.0, 0.35]),
+            Phosphor::Amber => Some([1.0, 0.7, 0.1]),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Phosphor::None => "None",
+            Phosphor::Green => "Green P1",
+            Phosphor::Amber => "Amber",
+        }
+    }
+}
+
+/// Upper bound on how often frames are drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FpsCap {
+    /// Draw on every animation frame.
+    #[default]
+    Off,
+    Fps30,
+    Fps60,
+    /// Hold to the measured display rate, dropping extra callbacks.
+    Display,
+}
+
+impl FpsCap {
+    pub const ALL: [FpsCap; 4] = [FpsCap::Off, FpsCap::Fps30, FpsCap::Fps60, FpsCap::Display];
+
+    /// Frame rate limit given the measured display rate, if any.
+    pub fn max_hz(self, display_hz: Option<f64>) -> Option<f64> {
+        match self {
+            FpsCap::Off => None,
+            FpsCap::Fps30 => Some(30.0),
+            FpsCap::Fps60 => Some(60.0),
+            FpsCap::Display => display_hz,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FpsCap::Off => "Off",
+            FpsCap::Fps30 => "30",
+            FpsCap::Fps60 => "60",
+            FpsCap::Display => "Display",
+        }
+    }
+}
+
+/// How fixed updates are scheduled against animation frames.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Stepping {
+    /// Accumulate wall-clock time and run exact 60 Hz steps.
+    #[default]
+    Exact,
+    /// One step per frame when the display measures ~60 Hz; the
+    /// accumulator otherwise, and for late frames.
+    Vsync,
+}
+
+impl Stepping {
+    pub const ALL: [Stepping; 2] = [Stepping::Exact, Stepping::Vsync];
+
+    /// Whether to run one step per frame given the measured display rate.
+    pub fn locked(self, display_hz: Option<f64>) -> bool {
+        /// How far from 60 Hz a display still counts as 60 Hz.
+        const TOLERANCE_HZ: f64 = 1.5;
+        self == Stepping::Vsync && display_hz.is_some_and(|hz| (hz - 60.0).abs() < TOLERANCE_HZ)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Stepping::Exact => "Exact 60 Hz",
+            Stepping::Vsync => "Vsync (auto)",
+        }
+    }
+}
+
+/// When the low-effects profile (no glow, capped frame rate) applies.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LowPower {
+    /// On battery below the threshold.
+    #[default]
+    Auto,
+    On,
+    Off,
+}
+
+impl LowPower {
+    pub const ALL: [LowPower; 3] = [LowPower::Auto, LowPower::On, LowPower::Off];
+
+    /// Whether the profile applies, given the battery reading.
+    pub fn active(self, battery_low: bool) -> bool {
+        match self {
+            LowPower::Auto => battery_low,
+            LowPower::On => true,
+            LowPower::Off => false,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LowPower::Auto => "Auto (battery)",
+            LowPower::On => "On",
+            LowPower::Off => "Off",
+        }
+    }
+}
+
+/// Corner for the "VectorCade" HUD; the control panel takes the other
+/// corner on the same edge.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HudCorner {
+    #[default]
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl HudCorner {
+    pub const ALL: [HudCorner; 4] = [
+        HudCorner::TopLeft,
+        HudCorner::TopRight,
+        HudCorner::BottomLeft,
+        HudCorner::BottomRight,
+    ];
+
+    /// CSS classes placing the HUD and the panel.
+    pub fn classes(self) -> (&'static str, &'static str) {
+        match self {
+            HudCorner::TopLeft => ("top-left", "top-right"),
+            HudCorner::TopRight => ("top-right", "top-left"),
+            HudCorner::BottomLeft => ("bottom-left", "bottom-right"),
+            HudCorner::BottomRight => ("bottom-right", "bottom-left"),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            HudCorner::TopLeft => "Top left",
+            HudCorner::TopRight => "Top right",
+            HudCorner::BottomLeft => "Bottom left",
+            HudCorner::BottomRight => "Bottom right",
+        }
+    }
+}
+
+/// Playfield rotation for vertical and cocktail cabinets.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Rotation {
+    #[default]
+    R0,
+    R90,
+    R180,
+    R270,
+}
+
+impl Rotation {
+    pub const ALL: [Rotation; 4] = [Rotation::R0, Rotation::R90, Rotation::R180, Rotation::R270];
+
+    /// Counter-clockwise quarter turns.
+    pub fn quarter_turns(self) -> u8 {
+        match self {
+            Rotation::R0 => 0,
+            Rotation::R90 => 1,
+            Rotation::R180 => 2,
+            Rotation::R270 => 3,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Rotation::R0 => "0\u{b0}",
+            Rotation::R90 => "90\u{b0}",
+            Rotation::R180 => "180\u{b0}",
+            Rotation::R270 => "270\u{b0}",
+        }
+    }
+}
+
+/// How the playfield maps onto the canvas.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScaleMode {
+    /// Whole playfield visible, letterboxed on the long axis.
+    #[default]
+    Fit,
+    /// No bars; the short axis is cropped.
+    Fill,
+    /// Whole-number multiples of a fixed logical size, for crisp lines.
+    Integer,
+}
+
+impl ScaleMode {
+    pub const ALL: [ScaleMode; 3] = [ScaleMode::Fit, ScaleMode::Fill, ScaleMode::Integer];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ScaleMode::Fit => "Fit",
+            ScaleMode::Fill => "Fill",
+            ScaleMode::Integer => "Integer",
+        }
+    }
+}
+
+/// What fills the canvas outside the playfield.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LetterboxBars {
+    /// The game's clear color runs to the canvas edges.
+    #[default]
+    GameClear,
+    /// `Settings::letterbox_color`.
+    Fixed,
+    /// The game's clear color, darkened.
+    Darkened,
+}
+
+impl LetterboxBars {
+    pub const ALL: [LetterboxBars; 3] = [
+        LetterboxBars::GameClear,
+        LetterboxBars::Fixed,
+        LetterboxBars::Darkened,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LetterboxBars::GameClear => "Game clear color",
+            LetterboxBars::Fixed => "Bar color",
+            LetterboxBars::Darkened => "Darkened game color",
+        }
+    }
+}
+
+/// Which key property drives game controls.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeyMapping {
+    /// Physical key position (`KeyboardEvent.code`): WASD is the same
+    /// keys on every layout.
+    #[default]
+    Physical,
+    /// The key's label on the user's layout (`KeyboardEvent.key`), for
+    /// AZERTY/Dvorak players who want the letters printed on their keys.
+    Logical,
+}
+
+impl KeyMapping {
+    pub const ALL: [KeyMapping; 2] = [KeyMapping::Physical, KeyMapping::Logical];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            KeyMapping::Physical => "Physical (position)",
+            KeyMapping::Logical => "Logical (key label)",
+        }
+    }
+}
+
+/// Platform settings shared by input, rendering, and the UI.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Show the HUD and control panel; `H` toggles this.
+    pub show_chrome: bool,
+    pub hud_corner: HudCorner,
+    /// Tint the HUD and panel with the selected game's theme color.
+    pub themed_chrome: bool,
+    /// Analog stick values below this magnitude read as zero.
+    pub deadzone: f32,
+    /// Response curve exponent for analog axes (1 = linear, 2 = squared, 3 = cubic).
+    pub curve_exponent: f32,
+    /// Negate the vertical stick axis (flight-style controls).
+    pub invert_y: bool,
+    /// Strip glow from every stroke for a crisp, glow-free look.
+    pub crisp_lines: bool,
+    /// Lower glow automatically while frames run over budget.
+    pub adaptive_glow: bool,
+    /// Low-effects profile for saving battery.
+    pub low_power: LowPower,
+    /// Snap vertices to a grid this many pixels wide (0 = off).
+    pub quantize_px: f32,
+    /// Draw strokes twice, a dim outer pass under a bright narrow core.
+    pub beam_core: bool,
+    /// Core width as a fraction of the stroke width.
+    pub beam_core_ratio: f32,
+    /// Draw all glow in a dimmer layer under the lines, so dense scenes
+    /// keep their color.
+    pub glow_layers: bool,
+    /// Treat game colors as linear light and convert them to sRGB.
+    pub linear_color: bool,
+    /// Hue rotations per second for games that opt in (0 = off).
+    pub palette_cycle_speed: f32,
+    /// Background used when a game clears with a fully transparent color,
+    /// or (under the always-clear policy) doesn't clear at all.
+    pub background: [f32; 3],
+    /// Draw a glowing frame around the playfield.
+    pub border: bool,
+    /// Color of the playfield frame.
+    pub border_color: [f32; 3],
+    /// Color of the frame's glow; differs from `border_color` for a two-tone
+    /// core-and-halo look.
+    pub border_glow_color: [f32; 3],
+    /// Show an on-screen D-pad and fire button; touches drive those
+    /// instead of swipes.
+    pub touch_controls: bool,
+    /// Map touch swipes to directions and taps to fire.
+    pub swipe_gestures: bool,
+    /// Minimum swipe distance in CSS pixels; shorter touches are taps.
+    pub swipe_threshold_px: f32,
+    /// Click to capture the mouse; relative motion moves a virtual aim point.
+    pub pointer_lock: bool,
+    /// Gain of the sound-effects bus.
+    pub sfx_volume: f32,
+    /// Gain of the music bus.
+    pub music_volume: f32,
+    /// Rumble gamepads (or vibrate phones) when a game asks for it.
+    pub haptics: bool,
+    /// Map game keys by physical position or by layout label.
+    pub key_mapping: KeyMapping,
+    /// Require a credit (coin key `5`) to start a game; off means free play.
+    pub credits: bool,
+    /// Consecutive frames the F9 frame dump captures.
+    pub frame_dump_count: f32,
+    /// Run every game briefly after start-up so first switches don't hitch.
+    pub warm_up: bool,
+    /// Seconds of "3-2-1" before play resumes from a pause (0 = instant).
+    pub resume_countdown_s: f32,
+    /// Seconds the "CONTINUE?" countdown runs after game over.
+    pub continue_s: f32,
+    /// Recolor everything in a single phosphor hue.
+    pub phosphor: Phosphor,
+    /// Seconds of coin-op self-test before the first game (0 = off).
+    pub self_test_s: f32,
+    /// Hold the start-up splash with the build commit, time and version.
+    pub boot_banner: bool,
+    /// Kiosk burn-in protection: how far the frame drifts, in pixels.
+    pub jitter_px: f32,
+    /// Kiosk burn-in protection: seconds per full drift cycle.
+    pub jitter_period_s: f32,
+    /// Frame rate cap to save battery on fast displays.
+    pub fps_cap: FpsCap,
+    /// Update scheduling: exact 60 Hz, or locked to vsync on 60 Hz displays.
+    pub stepping: Stepping,
+    /// Rotate the playfield; input directions follow the rotated view.
+    pub rotation: Rotation,
+    /// How the playfield is scaled to the canvas.
+    pub scale_mode: ScaleMode,
+    /// What fills the canvas outside the playfield.
+    pub letterbox: LetterboxBars,
+    pub letterbox_color: [f32; 3],
+    /// Seconds to trace a game's paths in after switching to it (0 = off).
+    pub reveal_s: f32,
+    /// Thousands of primitives a game may draw per frame before the rest
+    /// is dropped.
+    pub primitive_budget_k: f32,
+    /// Vectors the simulated beam can trace per frame; the rest flicker
+    /// dim (0 = off).
+    pub beam_vectors: f32,
+    /// Mirror the display left-right (input follows).
+    pub flip_x: bool,
+    /// Mirror the display top-bottom (input follows).
+    pub flip_y: bool,
+    /// Size game text relative to the playfield instead of in absolute pixels.
+    pub relative_text: bool,
+    /// Seconds without input before the display dims (0 = never).
+    pub idle_dim_s: f32,
+    /// Seconds the dim takes to fade to black.
+    pub idle_fade_s: f32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            show_chrome: true,
+            hud_corner: HudCorner::TopLeft,
+            themed_chrome: true,
+            deadzone: 0.15,
+            curve_exponent: 1.0,
+            invert_y: false,
+            crisp_lines: false,
+            adaptive_glow: true,
+            linear_color: false,
+            low_power: LowPower::Auto,
+            quantize_px: 0.0,
+            beam_core: false,
+            beam_core_ratio: 0.35,
+            glow_layers: false,
+            palette_cycle_speed: 0.0,
+            background: [0.0, 0.0, 0.0],
+            border: false,
+            border_color: [0.3, 0.6, 1.0],
+            border_glow_color: [0.3, 0.6, 1.0],
+            touch_controls: false,
+            swipe_gestures: false,
+            swipe_threshold_px: 30.0,
+            pointer_lock: false,
+            haptics: true,
+            sfx_volume: 1.0,
+            music_volume: 0.7,
+            key_mapping: KeyMapping::Physical,
+            credits: false,
+            continue_s: 10.0,
+            resume_countdown_s: 3.0,
+            frame_dump_count: 30.0,
+            warm_up: false,
+            phosphor: Phosphor::None,
+            self_test_s: 0.0,
+            boot_banner: false,
+            jitter_px: 3.0,
+            jitter_period_s: 120.0,
+            fps_cap: FpsCap::Off,
+            stepping: Stepping::Exact,
+            rotation: Rotation::R0,
+            scale_mode: ScaleMode::Fit,
+            letterbox: LetterboxBars::GameClear,
+            letterbox_color: [0.05, 0.05, 0.05],
+            reveal_s: 0.0,
+            primitive_budget_k: 200.0,
+            beam_vectors: 0.0,
+            relative_text: false,
+            flip_x: false,
+            flip_y: false,
+            idle_dim_s: 0.0,
+            idle_fade_s: 5.0,
+        }
+    }
+}
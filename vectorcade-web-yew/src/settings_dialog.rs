@@ -0,0 +1,340 @@
+//! Settings dialog bound to the live `Settings` in `GameState`.
+//!
+//! Edits go to the global settings, or with "Only for <game>" checked to the
+//! current game's overrides.
+
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::color;
+use crate::frame_dump;
+use crate::settings::{
+    FpsCap, HudCorner, KeyMapping, LetterboxBars, LowPower, Phosphor, Rotation, ScaleMode,
+    Settings, Stepping,
+};
+use crate::GAME_STATE;
+
+#[derive(Properties, PartialEq)]
+pub struct SettingsDialogProps {
+    pub on_close: Callback<MouseEvent>,
+}
+
+#[function_component(SettingsDialog)]
+pub fn settings_dialog(props: &SettingsDialogProps) -> Html {
+    let settings = use_state(|| GAME_STATE.with(|state| state.borrow().settings.clone()));
+    let this_game = use_state(|| false);
+    let (game_name, overridden) = GAME_STATE.with(|state| {
+        let state = state.borrow();
+        let name = state
+            .games
+            .get(state.selected)
+            .map_or("", |g| g.metadata().name);
+        (name, state.overrides.overridden(state.current_game_id()))
+    });
+
+    // Build an input handler that writes a slider value into one field.
+    let on_slider = |apply: fn(&mut Settings, f32)| {
+        let settings = settings.clone();
+        let this_game = this_game.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let value = input.value().parse::<f32>().unwrap_or_default();
+            let mut next = (*settings).clone();
+            apply(&mut next, value);
+            commit(&settings, next, *this_game);
+        })
+    };
+
+    // Build a change handler that writes a checkbox state into one field.
+    let on_toggle = |apply: fn(&mut Settings, bool)| {
+        let settings = settings.clone();
+        let this_game = this_game.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut next = (*settings).clone();
+            apply(&mut next, input.checked());
+            commit(&settings, next, *this_game);
+        })
+    };
+
+    // Build an input handler that writes a color picker value into one field.
+    let on_color = |apply: fn(&mut Settings, [f32; 3])| {
+        let settings = settings.clone();
+        let this_game = this_game.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let Some(rgb) = color::from_hex(&input.value()) {
+                let mut next = (*settings).clone();
+                apply(&mut next, rgb);
+                commit(&settings, next, *this_game);
+            }
+        })
+    };
+
+    // Build a change handler that writes a dropdown index into one field.
+    let on_choice = |apply: fn(&mut Settings, usize)| {
+        let settings = settings.clone();
+        let this_game = this_game.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            let idx = select.value().parse::<usize>().unwrap_or_default();
+            let mut next = (*settings).clone();
+            apply(&mut next, idx);
+            commit(&settings, next, *this_game);
+        })
+    };
+
+    let on_scope = {
+        let this_game = this_game.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            this_game.set(input.checked());
+        })
+    };
+    let on_reset_game = {
+        let settings = settings.clone();
+        Callback::from(move |_: MouseEvent| {
+            GAME_STATE.with(|state| {
+                let mut state = state.borrow_mut();
+                state.clear_game_settings();
+                settings.set(state.settings.clone());
+            });
+        })
+    };
+
+    html! {
+        <div class="dialog-overlay" onclick={props.on_close.clone()}>
+            <div class="dialog" onclick={Callback::from(|e: MouseEvent| e.stop_propagation())}>
+                <h2>{ "Settings" }</h2>
+
+                <div class="section">
+                    { checkbox(&format!("Only for {}", game_name), *this_game, on_scope) }
+                    if !overridden.is_empty() {
+                        <p class="overrides">
+                            { format!("Set for {}: {}", game_name, overridden.join(", ")) }
+                        </p>
+                        <button onclick={on_reset_game}>{ "Reset to global" }</button>
+                    }
+                </div>
+
+                <div class="section">
+                    <h3>{ "Layout" }</h3>
+                    { choice("HUD corner", &HudCorner::ALL.map(HudCorner::label),
+                        HudCorner::ALL.iter().position(|c| *c == settings.hud_corner).unwrap_or(0),
+                        on_choice(|s, i| s.hud_corner = HudCorner::ALL.get(i).copied().unwrap_or_default())) }
+                    { checkbox("Match game colors", settings.themed_chrome,
+                        on_toggle(|s, v| s.themed_chrome = v)) }
+                </div>
+
+                <div class="section">
+                    <h3>{ "Audio" }</h3>
+                    { slider("Effects volume", 0.0, 1.0, 0.05, settings.sfx_volume,
+                        on_slider(|s, v| s.sfx_volume = v)) }
+                    { slider("Music volume", 0.0, 1.0, 0.05, settings.music_volume,
+                        on_slider(|s, v| s.music_volume = v)) }
+                </div>
+
+                <div class="section">
+                    <h3>{ "Keyboard" }</h3>
+                    { choice("Key mapping", &KeyMapping::ALL.map(KeyMapping::label),
+                        KeyMapping::ALL.iter().position(|m| *m == settings.key_mapping).unwrap_or(0),
+                        on_choice(|s, i| s.key_mapping = KeyMapping::ALL.get(i).copied().unwrap_or_default())) }
+                </div>
+
+                <div class="section">
+                    <h3>{ "Analog Input" }</h3>
+                    { checkbox("Haptics (rumble / vibrate)", settings.haptics,
+                        on_toggle(|s, v| s.haptics = v)) }
+                    { slider("Deadzone", 0.0, 0.5, 0.01, settings.deadzone,
+                        on_slider(|s, v| s.deadzone = v)) }
+                    { slider("Curve", 1.0, 3.0, 0.1, settings.curve_exponent,
+                        on_slider(|s, v| s.curve_exponent = v)) }
+                    { checkbox("Invert Y", settings.invert_y,
+                        on_toggle(|s, v| s.invert_y = v)) }
+                </div>
+
+                <div class="section">
+                    <h3>{ "Touch" }</h3>
+                    { checkbox("On-screen D-pad and fire", settings.touch_controls,
+                        on_toggle(|s, v| s.touch_controls = v)) }
+                    { checkbox("Swipe gestures", settings.swipe_gestures,
+                        on_toggle(|s, v| s.swipe_gestures = v)) }
+                    { slider("Swipe distance", 10.0, 120.0, 5.0, settings.swipe_threshold_px,
+                        on_slider(|s, v| s.swipe_threshold_px = v)) }
+                </div>
+
+                <div class="section">
+                    <h3>{ "Mouse" }</h3>
+                    { checkbox("Pointer lock (click to capture)", settings.pointer_lock,
+                        on_toggle(|s, v| s.pointer_lock = v)) }
+                </div>
+
+                <div class="section">
+                    <h3>{ "Pause" }</h3>
+                    { slider("Resume countdown (s)", 0.0, 5.0, 1.0, settings.resume_countdown_s,
+                        on_slider(|s, v| s.resume_countdown_s = v)) }
+                </div>
+
+                <div class="section">
+                    <h3>{ "Cabinet" }</h3>
+                    { checkbox("Credits (coin key 5)", settings.credits,
+                        on_toggle(|s, v| s.credits = v)) }
+                    { slider("Continue (s)", 3.0, 30.0, 1.0, settings.continue_s,
+                        on_slider(|s, v| s.continue_s = v)) }
+                    { slider("Self-test (s)", 0.0, 8.0, 0.5, settings.self_test_s,
+                        on_slider(|s, v| s.self_test_s = v)) }
+                    { checkbox("Boot banner with build", settings.boot_banner,
+                        on_toggle(|s, v| s.boot_banner = v)) }
+                    { slider("Kiosk drift (px)", 0.0, 8.0, 1.0, settings.jitter_px,
+                        on_slider(|s, v| s.jitter_px = v)) }
+                    { slider("Drift period (s)", 30.0, 600.0, 30.0, settings.jitter_period_s,
+                        on_slider(|s, v| s.jitter_period_s = v)) }
+                    { slider("Dim after (s)", 0.0, 600.0, 30.0, settings.idle_dim_s,
+                        on_slider(|s, v| s.idle_dim_s = v)) }
+                    { slider("Dim fade (s)", 1.0, 30.0, 1.0, settings.idle_fade_s,
+                        on_slider(|s, v| s.idle_fade_s = v)) }
+                </div>
+
+                <div class="section">
+                    <h3>{ "Display" }</h3>
+                    { checkbox("Crisp lines (no glow)", settings.crisp_lines,
+                        on_toggle(|s, v| s.crisp_lines = v)) }
+                    { checkbox("Adaptive glow", settings.adaptive_glow,
+                        on_toggle(|s, v| s.adaptive_glow = v)) }
+                    { checkbox("Linear colors (to sRGB)", settings.linear_color,
+                        on_toggle(|s, v| s.linear_color = v)) }
+                    { checkbox("Beam core", settings.beam_core,
+                        on_toggle(|s, v| s.beam_core = v)) }
+                    { slider("Core width", 0.1, 0.9, 0.05, settings.beam_core_ratio,
+                        on_slider(|s, v| s.beam_core_ratio = v)) }
+                    { checkbox("Layer glow under lines", settings.glow_layers,
+                        on_toggle(|s, v| s.glow_layers = v)) }
+                    { slider("Palette cycle", 0.0, 1.0, 0.05, settings.palette_cycle_speed,
+                        on_slider(|s, v| s.palette_cycle_speed = v)) }
+                    { choice("Phosphor", &Phosphor::ALL.map(Phosphor::label),
+                        Phosphor::ALL.iter().position(|p| *p == settings.phosphor).unwrap_or(0),
+                        on_choice(|s, i| s.phosphor = Phosphor::ALL.get(i).copied().unwrap_or_default())) }
+                    { choice("FPS cap", &FpsCap::ALL.map(FpsCap::label),
+                        FpsCap::ALL.iter().position(|c| *c == settings.fps_cap).unwrap_or(0),
+                        on_choice(|s, i| s.fps_cap = FpsCap::ALL.get(i).copied().unwrap_or_default())) }
+                    { choice("Update stepping", &Stepping::ALL.map(Stepping::label),
+                        Stepping::ALL.iter().position(|m| *m == settings.stepping).unwrap_or(0),
+                        on_choice(|s, i| s.stepping = Stepping::ALL.get(i).copied().unwrap_or_default())) }
+                    { choice("Low power", &LowPower::ALL.map(LowPower::label),
+                        LowPower::ALL.iter().position(|p| *p == settings.low_power).unwrap_or(0),
+                        on_choice(|s, i| s.low_power = LowPower::ALL.get(i).copied().unwrap_or_default())) }
+                    { checkbox("Warm up games at start", settings.warm_up,
+                        on_toggle(|s, v| s.warm_up = v)) }
+                    { choice("Rotation", &Rotation::ALL.map(Rotation::label),
+                        Rotation::ALL.iter().position(|r| *r == settings.rotation).unwrap_or(0),
+                        on_choice(|s, i| s.rotation = Rotation::ALL.get(i).copied().unwrap_or_default())) }
+                    { slider("Title reveal (s)", 0.0, 3.0, 0.25, settings.reveal_s,
+                        on_slider(|s, v| s.reveal_s = v)) }
+                    { checkbox("Scale text with playfield", settings.relative_text,
+                        on_toggle(|s, v| s.relative_text = v)) }
+                    { slider("Primitive budget (k)", 10.0, 1000.0, 10.0, settings.primitive_budget_k,
+                        on_slider(|s, v| s.primitive_budget_k = v)) }
+                    { slider("Beam speed (vectors)", 0.0, 5000.0, 100.0, settings.beam_vectors,
+                        on_slider(|s, v| s.beam_vectors = v)) }
+                    { slider("Quantize grid (px)", 0.0, 16.0, 1.0, settings.quantize_px,
+                        on_slider(|s, v| s.quantize_px = v)) }
+                    { checkbox("Mirror horizontally", settings.flip_x,
+                        on_toggle(|s, v| s.flip_x = v)) }
+                    { checkbox("Mirror vertically", settings.flip_y,
+                        on_toggle(|s, v| s.flip_y = v)) }
+                    { choice("Scaling", &ScaleMode::ALL.map(ScaleMode::label),
+                        ScaleMode::ALL.iter().position(|m| *m == settings.scale_mode).unwrap_or(0),
+                        on_choice(|s, i| s.scale_mode = ScaleMode::ALL.get(i).copied().unwrap_or_default())) }
+                    { choice("Letterbox", &LetterboxBars::ALL.map(LetterboxBars::label),
+                        LetterboxBars::ALL.iter().position(|l| *l == settings.letterbox).unwrap_or(0),
+                        on_choice(|s, i| s.letterbox = LetterboxBars::ALL.get(i).copied().unwrap_or_default())) }
+                    { color_picker("Bar color", settings.letterbox_color,
+                        on_color(|s, v| s.letterbox_color = v)) }
+                </div>
+
+                <div class="section">
+                    <h3>{ "Capture" }</h3>
+                    { slider("Frame dump (F9)", 1.0, frame_dump::MAX_FRAMES as f32, 1.0,
+                        settings.frame_dump_count, on_slider(|s, v| s.frame_dump_count = v)) }
+                </div>
+
+                <div class="section">
+                    <h3>{ "Framing" }</h3>
+                    { color_picker("Background", settings.background,
+                        on_color(|s, v| s.background = v)) }
+                    { checkbox("Border", settings.border,
+                        on_toggle(|s, v| s.border = v)) }
+                    { color_picker("Border color", settings.border_color,
+                        on_color(|s, v| s.border_color = v)) }
+                    { color_picker("Border glow", settings.border_glow_color,
+                        on_color(|s, v| s.border_glow_color = v)) }
+                </div>
+
+                <button class="close-btn" onclick={props.on_close.clone()}>{ "Close" }</button>
+            </div>
+        </div>
+    }
+}
+
+/// Push an edit into the game state, globally or for the current game only,
+/// and re-render the dialog with the settings now in effect.
+fn commit(handle: &UseStateHandle<Settings>, next: Settings, this_game: bool) {
+    GAME_STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        state.edit_settings(handle, &next, this_game);
+        handle.set(state.settings.clone());
+    });
+}
+
+/// A labelled range input showing its current value.
+pub fn slider(
+    label: &str,
+    min: f32,
+    max: f32,
+    step: f32,
+    value: f32,
+    oninput: Callback<InputEvent>,
+) -> Html {
+    html! {
+        <label class="setting">
+            <span>{ label }</span>
+            <input type="range" min={min.to_string()} max={max.to_string()}
+                step={step.to_string()} value={value.to_string()} {oninput} />
+            <span class="value">{ format!("{:.2}", value) }</span>
+        </label>
+    }
+}
+
+/// A labelled checkbox.
+fn checkbox(label: &str, checked: bool, onchange: Callback<Event>) -> Html {
+    html! {
+        <label class="setting">
+            <span>{ label }</span>
+            <input type="checkbox" {checked} {onchange} />
+        </label>
+    }
+}
+
+/// A labelled color picker.
+fn color_picker(label: &str, rgb: [f32; 3], oninput: Callback<InputEvent>) -> Html {
+    html! {
+        <label class="setting">
+            <span>{ label }</span>
+            <input type="color" value={color::to_hex(rgb)} {oninput} />
+        </label>
+    }
+}
+
+/// A labelled dropdown over `options`, with `selected` as the current index.
+fn choice(label: &str, options: &[&str], selected: usize, onchange: Callback<Event>) -> Html {
+    html! {
+        <label class="setting">
+            <span>{ label }</span>
+            <select {onchange}>
+                { for options.iter().enumerate().map(|(i, option)| html!{
+                    <option value={i.to_string()} selected={i == selected}>{ *option }</option>
+                })}
+            </select>
+        </label>
+    }
+}
@@ -0,0 +1,240 @@
+//! Local multi-tab spectating over a `BroadcastChannel`.
+//!
+//! A tab opened with `?sync=play` broadcasts each run's seed and the input
+//! of every fixed step; tabs opened with `?sync=watch` replay them into the
+//! same game, so they show the same run. This relies on games being
+//! deterministic given the seed and per-step input: games that steer by
+//! `now_s`, or draw random numbers while rendering, drift apart.
+//!
+//! Messages carry a sequence number. Out-of-order ones are buffered; a lost
+//! step can't be recovered, so after `MAX_GAP` messages without it the
+//! spectator waits for the next run start.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+use vectorcade_shared::input::{Axis, Button, InputState, Key, Pointer};
+use wasm_bindgen::prelude::*;
+use web_sys::{BroadcastChannel, MessageEvent};
+
+use crate::{logging, query};
+
+const CHANNEL: &str = "vectorcade-sync";
+/// Keys carried in a snapshot, by bit position.
+const KEYS: [Key; 12] = [
+    Key::Left,
+    Key::Right,
+    Key::Up,
+    Key::Down,
+    Key::W,
+    Key::S,
+    Key::Space,
+    Key::Enter,
+    Key::Escape,
+    Key::Z,
+    Key::X,
+    Key::C,
+];
+const AXES: [Axis; 3] = [Axis::MoveX, Axis::MoveY, Axis::Thrust];
+/// Messages buffered past a missing one before giving up on it.
+const MAX_GAP: usize = 120;
+
+/// The input one fixed step saw, replayable as an `InputState`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Bit masks over `KEYS`.
+    down: u16,
+    pressed: u16,
+    released: u16,
+    axes: [f32; 3],
+    pointer: Option<([f32; 2], bool)>,
+}
+
+impl Snapshot {
+    pub fn capture(input: &dyn InputState) -> Self {
+        let mut snapshot = Self::default();
+        for (bit, key) in KEYS.into_iter().enumerate() {
+            let button = input.key(key);
+            let mask = 1 << bit;
+            snapshot.down |= if button.is_down { mask } else { 0 };
+            snapshot.pressed |= if button.went_down { mask } else { 0 };
+            snapshot.released |= if button.went_up { mask } else { 0 };
+        }
+        snapshot.axes = AXES.map(|a| input.axis(a));
+        snapshot.pointer = input.pointer().map(|p| (p.pos.to_array(), p.down));
+        snapshot
+    }
+}
+
+impl InputState for Snapshot {
+    fn key(&self, k: Key) -> Button {
+        let Some(bit) = KEYS.iter().position(|key| *key == k) else {
+            return Button {
+                is_down: false,
+                went_down: false,
+                went_up: false,
+            };
+        };
+        let set = |mask: u16| mask & (1 << bit) != 0;
+        Button {
+            is_down: set(self.down),
+            went_down: set(self.pressed),
+            went_up: set(self.released),
+        }
+    }
+
+    fn axis(&self, a: Axis) -> f32 {
+        // Same order as `AXES`.
+        match a {
+            Axis::MoveX => self.axes[0],
+            Axis::MoveY => self.axes[1],
+            Axis::Thrust => self.axes[2],
+            _ => 0.0,
+        }
+    }
+
+    fn pointer(&self) -> Option<Pointer> {
+        self.pointer.map(|(pos, down)| Pointer {
+            pos: Vec2::from(pos),
+            down,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum Event {
+    /// A run of `game` starts from a reset with `seed`.
+    Start { game: String, seed: u64 },
+    /// One fixed update step.
+    Step(Snapshot),
+}
+
+#[derive(Serialize, Deserialize)]
+struct Message {
+    seq: u64,
+    event: Event,
+}
+
+/// This tab's side of the sync, from `?sync=play` or `?sync=watch`.
+pub enum Spectate {
+    Play(Broadcaster),
+    Watch(Spectator),
+}
+
+impl Spectate {
+    pub fn from_url() -> Option<Self> {
+        let role = query::param("sync")?;
+        let channel = match BroadcastChannel::new(CHANNEL) {
+            Ok(channel) => channel,
+            Err(e) => {
+                logging::warn(&format!("sync unavailable: {:?}", e));
+                return None;
+            }
+        };
+        match role.as_str() {
+            "play" => Some(Self::Play(Broadcaster { channel, seq: 0 })),
+            "watch" => Some(Self::Watch(Spectator::listen(channel))),
+            _ => None,
+        }
+    }
+}
+
+pub struct Broadcaster {
+    channel: BroadcastChannel,
+    seq: u64,
+}
+
+impl Broadcaster {
+    pub fn send(&mut self, event: Event) {
+        let msg = Message {
+            seq: self.seq,
+            event,
+        };
+        self.seq += 1;
+        match serde_json::to_string(&msg) {
+            Ok(json) => {
+                if let Err(e) = self.channel.post_message(&JsValue::from_str(&json)) {
+                    logging::warn(&format!("sync post failed: {:?}", e));
+                }
+            }
+            Err(e) => logging::warn(&format!("sync encode failed: {}", e)),
+        }
+    }
+}
+
+pub struct Spectator {
+    inbox: Rc<RefCell<BTreeMap<u64, Event>>>,
+    /// Sequence number to apply next; `None` until a run start arrives.
+    next: Option<u64>,
+    _channel: BroadcastChannel,
+    _on_message: Closure<dyn FnMut(MessageEvent)>,
+}
+
+impl Spectator {
+    fn listen(channel: BroadcastChannel) -> Self {
+        let inbox: Rc<RefCell<BTreeMap<u64, Event>>> = Rc::default();
+        let on_message = {
+            let inbox = inbox.clone();
+            Closure::<dyn FnMut(MessageEvent)>::new(move |e: MessageEvent| {
+                let Some(json) = e.data().as_string() else {
+                    return;
+                };
+                match serde_json::from_str::<Message>(&json) {
+                    Ok(msg) => {
+                        inbox.borrow_mut().insert(msg.seq, msg.event);
+                    }
+                    Err(e) => logging::warn(&format!("ignoring sync message: {}", e)),
+                }
+            })
+        };
+        channel.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        Self {
+            inbox,
+            next: None,
+            _channel: channel,
+            _on_message: on_message,
+        }
+    }
+
+    /// Events ready to apply, in order.
+    pub fn poll(&mut self) -> Vec<Event> {
+        let mut inbox = self.inbox.borrow_mut();
+        let stuck = match self.next {
+            None => true,
+            Some(next) => !inbox.contains_key(&next) && inbox.len() > MAX_GAP,
+        };
+        if stuck {
+            // Drop everything before the oldest run start and resume there.
+            let start = inbox
+                .iter()
+                .find(|(_, event)| matches!(event, Event::Start { .. }))
+                .map(|(seq, _)| *seq);
+            if self.next.is_some() {
+                logging::warn("sync lost a step; waiting for the next run");
+                self.next = None;
+            }
+            match start {
+                Some(seq) => {
+                    *inbox = inbox.split_off(&seq);
+                    self.next = Some(seq);
+                }
+                None => {
+                    inbox.clear();
+                    return Vec::new();
+                }
+            }
+        }
+        let mut ready = Vec::new();
+        while let Some(next) = self.next {
+            let Some(event) = inbox.remove(&next) else {
+                break;
+            };
+            ready.push(event);
+            self.next = Some(next + 1);
+        }
+        ready
+    }
+}
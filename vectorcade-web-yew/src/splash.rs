@@ -0,0 +1,101 @@
+//! Vector "VECTORCADE" splash shown until the first game frame.
+//!
+//! With the boot banner setting on, the splash also shows the build commit,
+//! time and crate version like a firmware banner, and holds for a moment so
+//! the build can be read off a screenshot; any key fades it early.
+
+use glam::Vec2;
+use vectorcade_shared::draw::DrawCmd;
+use vectorcade_shared::game::ScreenInfo;
+use vectorcade_shared::Rgba;
+
+use crate::{overlay, BUILD_GIT_SHA, BUILD_TIMESTAMP};
+
+/// How long the splash takes to fade out once the game has drawn (ms).
+const FADE_MS: f64 = 600.0;
+/// How long the boot banner holds before fading (ms).
+const BANNER_HOLD_MS: f64 = 2500.0;
+
+/// Tracks splash visibility across frames.
+#[derive(Default)]
+pub struct Splash {
+    /// When the splash was first drawn.
+    shown_at: Option<f64>,
+    fade_start: Option<f64>,
+    done: bool,
+}
+
+impl Splash {
+    /// Draw the splash over `out` if it is still visible.
+    ///
+    /// `game_drew` reports whether the game produced any commands this frame;
+    /// the first frame where it did starts the fade, or with `banner` the
+    /// first such frame once the banner has held.
+    pub fn draw(
+        &mut self,
+        now: f64,
+        game_drew: bool,
+        banner: bool,
+        screen: &ScreenInfo,
+        out: &mut Vec<DrawCmd>,
+    ) {
+        if self.done {
+            return;
+        }
+        let shown_at = *self.shown_at.get_or_insert(now);
+        let held = !banner || now - shown_at >= BANNER_HOLD_MS;
+        if game_drew && held && self.fade_start.is_none() {
+            self.fade_start = Some(now);
+        }
+        let alpha = match self.fade_start {
+            Some(start) => (1.0 - (now - start) / FADE_MS) as f32,
+            None => 1.0,
+        };
+        if alpha <= 0.0 {
+            self.done = true;
+            return;
+        }
+        if !game_drew || (banner && self.fade_start.is_none()) {
+            // First, so the overlays already in `out` stay visible.
+            out.insert(
+                0,
+                DrawCmd::Clear {
+                    color: Rgba(0.0, 0.0, 0.0, 1.0),
+                },
+            );
+        }
+
+        let color = Rgba(0.3, 1.0, 0.6, alpha);
+        overlay::text_centered(out, screen, Vec2::new(0.0, 0.05), "VECTORCADE", 48.0, color);
+        let w = overlay::text_width(screen, "VECTORCADE", 48.0) * 0.5;
+        overlay::line(out, Vec2::new(-w, -0.08), Vec2::new(w, -0.08), color, 2.0);
+        if banner {
+            let dim = Rgba(0.3, 1.0, 0.6, alpha * 0.7);
+            let lines = [
+                format!("V{}", env!("CARGO_PKG_VERSION")),
+                format!("BUILD {}", BUILD_GIT_SHA.to_uppercase()),
+                BUILD_TIMESTAMP.to_string(),
+            ];
+            let step = 14.0 * overlay::LINE_ADVANCE * overlay::ndc_per_px(screen);
+            for (i, line) in lines.iter().enumerate() {
+                let at = Vec2::new(0.0, -0.2 - step * i as f32);
+                overlay::text_centered(out, screen, at, line, 14.0, dim);
+            }
+        }
+    }
+
+    /// Whether the boot banner is holding, with the game hidden behind it.
+    pub fn holding(&self, banner: bool) -> bool {
+        banner && !self.done && self.fade_start.is_none()
+    }
+
+    /// Start the fade now if the banner is holding. Returns false if the
+    /// splash was already fading or gone.
+    pub fn skip(&mut self, now: f64) -> bool {
+        if self.done || self.shown_at.is_none() || self.fade_start.is_some() {
+            return false;
+        }
+        self.fade_start = Some(now);
+        true
+    }
+}
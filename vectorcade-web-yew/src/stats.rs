@@ -0,0 +1,101 @@
+//! Per-game play statistics persisted in `localStorage`.
+//!
+//! Stored as a JSON map keyed by `GameMeta::id`. Every field has a serde
+//! default, so saves from older builds load with new stats at zero.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::storage;
+
+/// `localStorage` key for saved statistics.
+const STORAGE_KEY: &str = "vectorcade.stats";
+
+/// Seconds of play between saves while a game runs.
+const SAVE_INTERVAL_S: f64 = 10.0;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameStats {
+    pub plays: u32,
+    pub seconds_played: f64,
+    /// Best score seen; stays empty until a score is reported.
+    pub best_score: Option<u64>,
+    /// Initials entered with the best score.
+    pub best_initials: String,
+}
+
+/// Statistics for every game, loaded once and saved as they change.
+#[derive(Default)]
+pub struct StatsBook {
+    games: BTreeMap<String, GameStats>,
+    unsaved_s: f64,
+    /// Keep changes in memory only; see `detach`.
+    detached: bool,
+}
+
+impl StatsBook {
+    pub fn load() -> Self {
+        Self {
+            games: storage::load(STORAGE_KEY).unwrap_or_default(),
+            unsaved_s: 0.0,
+            detached: false,
+        }
+    }
+
+    /// Stop saving: the whole map goes under one key, so a second writer
+    /// would overwrite the first one's counts.
+    pub fn detach(&mut self) {
+        self.detached = true;
+    }
+
+    pub fn get(&self, id: &str) -> GameStats {
+        self.games.get(id).cloned().unwrap_or_default()
+    }
+
+    /// Count a new play of `id`.
+    pub fn record_play(&mut self, id: &str) {
+        self.entry(id).plays += 1;
+        self.save();
+    }
+
+    /// Add `dt` seconds of play time to `id`.
+    pub fn add_time(&mut self, id: &str, dt: f32) {
+        self.entry(id).seconds_played += dt as f64;
+        self.unsaved_s += dt as f64;
+        if self.unsaved_s >= SAVE_INTERVAL_S {
+            self.save();
+        }
+    }
+
+    /// Store a new best score for `id` with the player's initials.
+    pub fn record_best(&mut self, id: &str, score: u64, initials: &str) {
+        let stats = self.entry(id);
+        stats.best_score = Some(score);
+        stats.best_initials = initials.to_string();
+        self.save();
+    }
+
+    fn entry(&mut self, id: &str) -> &mut GameStats {
+        self.games.entry(id.to_string()).or_default()
+    }
+
+    fn save(&mut self) {
+        if !self.detached {
+            storage::save(STORAGE_KEY, &self.games);
+        }
+        self.unsaved_s = 0.0;
+    }
+}
+
+/// Format seconds as `h:mm:ss`, or `m:ss` under an hour.
+pub fn format_duration(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    let (h, m, s) = (total / 3600, total / 60 % 60, total % 60);
+    if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{}:{:02}", m, s)
+    }
+}
@@ -0,0 +1,49 @@
+//! JSON values persisted in `localStorage`.
+//!
+//! Storage can be missing (private browsing, sandboxed iframes) or full; both
+//! are logged and otherwise ignored so the platform keeps running.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::logging;
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+/// Load and decode the value stored under `key`, if any.
+pub fn load<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let raw = local_storage()?.get_item(key).ok().flatten()?;
+    match serde_json::from_str(&raw) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            logging::warn(&format!("ignoring unreadable {}: {}", key, e));
+            None
+        }
+    }
+}
+
+/// Encode `value` and store it under `key`.
+pub fn save<T: Serialize>(key: &str, value: &T) {
+    let Some(storage) = local_storage() else {
+        return;
+    };
+    let raw = match serde_json::to_string(value) {
+        Ok(raw) => raw,
+        Err(e) => {
+            logging::warn(&format!("could not encode {}: {}", key, e));
+            return;
+        }
+    };
+    if storage.set_item(key, &raw).is_err() {
+        logging::warn(&format!("could not save {}", key));
+    }
+}
+
+/// Delete the value stored under `key`.
+pub fn remove(key: &str) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.remove_item(key);
+    }
+}
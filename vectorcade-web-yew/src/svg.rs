@@ -0,0 +1,191 @@
+//! SVG snapshots of the current frame, for posters and documentation.
+//!
+//! The display list is already vector data, so it maps straight onto SVG:
+//! lines and polylines become `<line>` / `<polyline>`, glow becomes a blur
+//! filter under the stroke, and the last `Clear` becomes a background rect.
+//! NDC maps to pixels the way the overlays assume (shorter axis spans -1..1).
+//! Text is written as `<text>` in a monospace font, since the vector glyphs
+//! live inside the renderer.
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use glam::Vec2;
+use vectorcade_shared::draw::{DrawCmd, Stroke};
+use vectorcade_shared::game::ScreenInfo;
+use vectorcade_shared::Rgba;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+
+use crate::display_list::GLOW_REACH_PX;
+use crate::GAME_STATE;
+
+/// Glow amounts are rounded to this step so similar strokes share a filter.
+const GLOW_STEP: f32 = 0.25;
+
+/// Render a display list as a standalone SVG document.
+pub fn to_svg(cmds: &[DrawCmd], screen: &ScreenInfo) -> String {
+    let (w, h) = (
+        screen.width_px.max(1) as f32,
+        screen.height_px.max(1) as f32,
+    );
+    let half_short = w.min(h) * 0.5;
+    let to_px = |p: Vec2| (w * 0.5 + p.x * half_short, h * 0.5 - p.y * half_short);
+
+    // Anything before the last clear would have been wiped.
+    let start = cmds
+        .iter()
+        .rposition(|cmd| matches!(cmd, DrawCmd::Clear { .. }))
+        .unwrap_or(0);
+    let cmds = &cmds[start..];
+
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#
+    );
+    let glows: BTreeSet<u32> = cmds
+        .iter()
+        .filter_map(stroke)
+        .filter_map(glow_level)
+        .collect();
+    if !glows.is_empty() {
+        out.push_str("<defs>");
+        for level in &glows {
+            let reach = *level as f32 * GLOW_STEP * GLOW_REACH_PX * screen.dpi_scale;
+            // The filter reach is roughly three standard deviations.
+            let _ = write!(
+                out,
+                r#"<filter id="glow-{level}" x="-50%" y="-50%" width="200%" height="200%"><feGaussianBlur stdDeviation="{:.2}"/></filter>"#,
+                reach / 3.0
+            );
+        }
+        out.push_str("</defs>");
+    }
+
+    for cmd in cmds {
+        match cmd {
+            DrawCmd::Clear { color } => {
+                let _ = write!(
+                    out,
+                    r#"<rect width="100%" height="100%" {}/>"#,
+                    paint("fill", *color)
+                );
+            }
+            DrawCmd::Line(line) => {
+                let (ax, ay) = to_px(line.a);
+                let (bx, by) = to_px(line.b);
+                let shape = format!(r#"line x1="{ax:.2}" y1="{ay:.2}" x2="{bx:.2}" y2="{by:.2}""#);
+                stroked(&mut out, &shape, &line.stroke, screen);
+            }
+            DrawCmd::Polyline {
+                pts,
+                closed,
+                stroke,
+            } => {
+                let points: Vec<String> = pts
+                    .iter()
+                    .map(|p| {
+                        let (x, y) = to_px(*p);
+                        format!("{x:.2},{y:.2}")
+                    })
+                    .collect();
+                let tag = if *closed { "polygon" } else { "polyline" };
+                let shape = format!(r#"{tag} points="{}""#, points.join(" "));
+                stroked(&mut out, &shape, stroke, screen);
+            }
+            DrawCmd::Text {
+                pos,
+                text,
+                size_px,
+                color,
+                ..
+            } => {
+                let (x, y) = to_px(*pos);
+                let _ = write!(
+                    out,
+                    r#"<text x="{x:.2}" y="{y:.2}" font-family="monospace" font-size="{:.1}" {}>{}</text>"#,
+                    size_px * screen.dpi_scale,
+                    paint("fill", *color),
+                    escape(text)
+                );
+            }
+            _ => {}
+        }
+    }
+    out.push_str("</svg>");
+    out
+}
+
+fn stroke(cmd: &DrawCmd) -> Option<&Stroke> {
+    match cmd {
+        DrawCmd::Line(line) => Some(&line.stroke),
+        DrawCmd::Polyline { stroke, .. } => Some(stroke),
+        _ => None,
+    }
+}
+
+fn glow_level(stroke: &Stroke) -> Option<u32> {
+    let level = (stroke.glow / GLOW_STEP).round();
+    (level >= 1.0).then_some(level as u32)
+}
+
+/// Write `shape` (an element name and its geometry attributes) with the
+/// stroke applied, preceded by a blurred copy when the stroke glows.
+fn stroked(out: &mut String, shape: &str, stroke: &Stroke, screen: &ScreenInfo) {
+    let attrs = format!(
+        r#"fill="none" stroke-width="{:.2}" stroke-linecap="round" stroke-linejoin="round" {}"#,
+        stroke.width_px * screen.dpi_scale,
+        paint("stroke", stroke.color)
+    );
+    if let Some(level) = glow_level(stroke) {
+        let _ = write!(out, r#"<{shape} {attrs} filter="url(#glow-{level})"/>"#);
+    }
+    let _ = write!(out, "<{shape} {attrs}/>");
+}
+
+/// An SVG paint attribute plus its opacity, from a 0..1 color.
+fn paint(attr: &str, c: Rgba) -> String {
+    let channel = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!(
+        r#"{attr}="rgb({},{},{})" {attr}-opacity="{:.3}""#,
+        channel(c.0),
+        channel(c.1),
+        channel(c.2),
+        c.3.clamp(0.0, 1.0)
+    )
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// The last frame as an SVG document.
+#[wasm_bindgen]
+pub fn frame_svg() -> String {
+    GAME_STATE.with(|state| {
+        let state = state.borrow();
+        to_svg(&state.draw_cmds, &state.screen)
+    })
+}
+
+/// Offer the last frame as an SVG download.
+pub fn download() -> Result<(), JsValue> {
+    let parts = js_sys::Array::of1(&JsValue::from_str(&frame_svg()));
+    let props = BlobPropertyBag::new();
+    props.set_type("image/svg+xml");
+    let blob = Blob::new_with_str_sequence_and_options(&parts, &props)?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+
+    let document = web_sys::window()
+        .and_then(|w| w.document())
+        .ok_or_else(|| JsValue::from_str("no document"))?;
+    let link: HtmlAnchorElement = document.create_element("a")?.dyn_into()?;
+    link.set_href(&url);
+    link.set_download("vectorcade.svg");
+    link.click();
+    Url::revoke_object_url(&url)
+}
@@ -0,0 +1,114 @@
+//! Pixel snapping for stationary text.
+//!
+//! Text that holds still is snapped to the device pixel grid so it renders
+//! identically every frame. Moving text keeps its exact float position:
+//! snapping it would make it step from pixel to pixel and shimmer.
+
+use std::collections::HashMap;
+
+use glam::Vec2;
+use vectorcade_shared::draw::DrawCmd;
+use vectorcade_shared::game::ScreenInfo;
+
+use crate::scaling::ViewTransform;
+
+/// Text positions from the previous frame, keyed by string and by which
+/// occurrence of that string it was, so repeated strings ("0" for both
+/// scores) are told apart.
+#[derive(Default)]
+pub struct TextSnap {
+    last: HashMap<(String, usize), Vec2>,
+}
+
+impl TextSnap {
+    /// Snap every text command that sat at the same position last frame.
+    pub fn apply(&mut self, cmds: &mut [DrawCmd], screen: &ScreenInfo) {
+        if screen.width_px == 0 || screen.height_px == 0 {
+            return;
+        }
+        let view = ViewTransform::new(screen, 1.0);
+        let mut seen = HashMap::with_capacity(self.last.len());
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for cmd in cmds {
+            if let DrawCmd::Text { pos, text, .. } = cmd {
+                let raw = *pos;
+                let count = counts.entry(text.clone()).or_insert(0);
+                let key = (text.clone(), *count);
+                *count += 1;
+                if self.last.get(&key) == Some(&raw) {
+                    *pos = view.from_px(view.to_px(raw).round());
+                }
+                seen.insert(key, raw);
+            }
+        }
+        self.last = seen;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vectorcade_shared::font::FontStyleId;
+    use vectorcade_shared::Rgba;
+
+    fn text(s: &str, pos: Vec2) -> DrawCmd {
+        DrawCmd::Text {
+            pos,
+            text: s.to_string(),
+            size_px: 16.0,
+            color: Rgba(1.0, 1.0, 1.0, 1.0),
+            style: FontStyleId::ATARI,
+        }
+    }
+
+    fn positions(cmds: &[DrawCmd]) -> Vec<Vec2> {
+        cmds.iter()
+            .filter_map(|cmd| match cmd {
+                DrawCmd::Text { pos, .. } => Some(*pos),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stationary_text_lands_on_pixels_of_a_wide_screen() {
+        let screen = ScreenInfo {
+            width_px: 1920,
+            height_px: 1080,
+            dpi_scale: 1.0,
+        };
+        let view = ViewTransform::new(&screen, 1.0);
+        let frame = || {
+            vec![
+                text("0", Vec2::new(-0.5013, 0.9)),
+                text("0", Vec2::new(0.5027, 0.9)),
+            ]
+        };
+        let mut snap = TextSnap::default();
+        snap.apply(&mut frame(), &screen);
+        let mut cmds = frame();
+        snap.apply(&mut cmds, &screen);
+        let snapped = positions(&cmds);
+        // Both repeated strings snap, each near where it was.
+        assert!(snapped[0].distance(Vec2::new(-0.5013, 0.9)) < 2.0 / 1080.0);
+        assert!(snapped[1].distance(Vec2::new(0.5027, 0.9)) < 2.0 / 1080.0);
+        for p in snapped {
+            let px = view.to_px(p);
+            assert!((px - px.round()).abs().max_element() < 1e-3, "{:?}", px);
+        }
+    }
+
+    #[test]
+    fn moving_text_is_left_alone() {
+        let screen = ScreenInfo {
+            width_px: 1920,
+            height_px: 1080,
+            dpi_scale: 1.0,
+        };
+        let mut snap = TextSnap::default();
+        snap.apply(&mut [text("SHIP", Vec2::new(0.1, 0.2))], &screen);
+        let mut cmds = [text("SHIP", Vec2::new(0.1013, 0.2))];
+        snap.apply(&mut cmds, &screen);
+        assert_eq!(positions(&cmds), vec![Vec2::new(0.1013, 0.2)]);
+    }
+}
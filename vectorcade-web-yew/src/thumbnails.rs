@@ -0,0 +1,91 @@
+//! Static per-game thumbnails for game-picker UIs.
+//!
+//! A fresh instance of the game is reset and stepped with no input for a
+//! warmup period, then one frame is rendered to a small offscreen canvas and
+//! returned as a PNG data URL. Results are cached per game id and warmup
+//! count, so rebuilding the registry doesn't mix games up, and one offscreen
+//! renderer is shared by every thumbnail.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use vectorcade_render_wgpu::{VectorRenderer, WgpuRenderer};
+use vectorcade_shared::game::ScreenInfo;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlCanvasElement;
+
+use crate::{headless, scripted};
+
+const THUMB_WIDTH: u32 = 320;
+const THUMB_HEIGHT: u32 = 240;
+/// Fixed seed so a game's thumbnail is the same on every load.
+const THUMB_SEED: u64 = 42;
+/// Warmup used when the caller passes zero.
+const DEFAULT_WARMUP_FRAMES: u32 = 120;
+
+thread_local! {
+    static CACHE: RefCell<HashMap<(String, u32), String>> = RefCell::new(HashMap::new());
+    /// Offscreen canvas and renderer, created with the first thumbnail.
+    static RENDERER: RefCell<Option<(HtmlCanvasElement, WgpuRenderer)>> =
+        const { RefCell::new(None) };
+}
+
+/// Drop cached thumbnails of `id`, e.g. after its definition is replaced.
+pub fn forget(id: &str) {
+    CACHE.with(|c| c.borrow_mut().retain(|(cached, _), _| cached != id));
+}
+
+/// Render a thumbnail of game `index` after `warmup_frames` idle updates.
+#[wasm_bindgen]
+pub async fn game_thumbnail(index: usize, warmup_frames: u32) -> Result<String, JsValue> {
+    let warmup = if warmup_frames == 0 {
+        DEFAULT_WARMUP_FRAMES
+    } else {
+        warmup_frames
+    };
+    let mut game = scripted::registry()
+        .into_iter()
+        .nth(index)
+        .ok_or_else(|| JsValue::from_str("no game at that index"))?;
+    let key = (game.metadata().id.to_string(), warmup);
+    if let Some(url) = CACHE.with(|c| c.borrow().get(&key).cloned()) {
+        return Ok(url);
+    }
+    let screen = ScreenInfo {
+        width_px: THUMB_WIDTH,
+        height_px: THUMB_HEIGHT,
+        dpi_scale: 1.0,
+    };
+    let cmds = headless::run_headless(game.as_mut(), screen, THUMB_SEED, warmup as usize);
+
+    if RENDERER.with(|r| r.borrow().is_none()) {
+        let created = create_renderer().await?;
+        // Another thumbnail may have created one meanwhile; keep the first.
+        RENDERER.with(|r| {
+            r.borrow_mut().get_or_insert(created);
+        });
+    }
+    let url = RENDERER.with(|r| {
+        let mut slot = r.borrow_mut();
+        let (canvas, renderer) = slot.as_mut().expect("renderer created above");
+        renderer.render(&cmds);
+        canvas.to_data_url()
+    })?;
+
+    CACHE.with(|c| c.borrow_mut().insert(key, url.clone()));
+    Ok(url)
+}
+
+async fn create_renderer() -> Result<(HtmlCanvasElement, WgpuRenderer), JsValue> {
+    let document = web_sys::window()
+        .and_then(|w| w.document())
+        .ok_or_else(|| JsValue::from_str("no document"))?;
+    let canvas: HtmlCanvasElement = document.create_element("canvas")?.dyn_into()?;
+    canvas.set_width(THUMB_WIDTH);
+    canvas.set_height(THUMB_HEIGHT);
+    let renderer = WgpuRenderer::new_web(canvas.clone(), THUMB_WIDTH, THUMB_HEIGHT)
+        .await
+        .map_err(|e| JsValue::from_str(&format!("{:?}", e)))?;
+    Ok((canvas, renderer))
+}
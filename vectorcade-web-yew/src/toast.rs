@@ -0,0 +1,42 @@
+//! Short on-canvas status messages drawn in vector text.
+
+use glam::Vec2;
+use vectorcade_shared::draw::DrawCmd;
+use vectorcade_shared::game::ScreenInfo;
+use vectorcade_shared::Rgba;
+
+use crate::overlay;
+
+/// How long a message stays fully visible (ms).
+const HOLD_MS: f64 = 1_200.0;
+/// Fade-out time after the hold (ms).
+const FADE_MS: f64 = 400.0;
+/// Text size and baseline height (NDC) of the message.
+pub const SIZE_PX: f32 = 20.0;
+pub const Y: f32 = -0.85;
+
+/// A single message slot; a new message replaces the current one.
+#[derive(Default)]
+pub struct Toast {
+    message: Option<(String, f64)>,
+}
+
+impl Toast {
+    pub fn show(&mut self, msg: impl Into<String>, now: f64) {
+        self.message = Some((msg.into(), now));
+    }
+
+    pub fn draw(&mut self, now: f64, screen: &ScreenInfo, out: &mut Vec<DrawCmd>) {
+        let Some((msg, shown_at)) = &self.message else {
+            return;
+        };
+        let age = now - shown_at;
+        if age > HOLD_MS + FADE_MS {
+            self.message = None;
+            return;
+        }
+        let alpha = (1.0 - (age - HOLD_MS).max(0.0) / FADE_MS) as f32;
+        let color = Rgba(1.0, 1.0, 0.6, alpha);
+        overlay::text_centered(out, screen, Vec2::new(0.0, Y), msg, SIZE_PX, color);
+    }
+}
@@ -0,0 +1,121 @@
+//! On-screen D-pad and fire button for touch play.
+//!
+//! Each finger is tracked by `pointerId`, so a thumb on the D-pad and
+//! another on fire register at the same time. The keys held are the union
+//! over all active touches: a key is released only once no touch is on its
+//! region any more.
+
+use std::collections::{HashMap, HashSet};
+
+use glam::Vec2;
+use vectorcade_shared::draw::DrawCmd;
+use vectorcade_shared::input::Key;
+use vectorcade_shared::Rgba;
+
+use crate::input::KeyboardInput;
+use crate::overlay;
+
+const DPAD_CENTER: Vec2 = Vec2::new(-0.7, -0.6);
+const DPAD_RADIUS: f32 = 0.25;
+/// Touches this close to the D-pad center pick no direction.
+const DPAD_DEAD: f32 = 0.05;
+const FIRE_CENTER: Vec2 = Vec2::new(0.7, -0.6);
+const FIRE_RADIUS: f32 = 0.15;
+
+#[derive(Default)]
+pub struct TouchControls {
+    /// Active touches in backing-store pixels, by `pointerId`.
+    touches: HashMap<i32, Vec2>,
+    /// Keys the controls are holding down.
+    held: HashSet<Key>,
+}
+
+impl TouchControls {
+    /// Move touch `id` to `pos_px`, or end it with `None`.
+    pub fn set_touch(&mut self, id: i32, pos_px: Option<Vec2>) {
+        match pos_px {
+            Some(pos) => self.touches.insert(id, pos),
+            None => self.touches.remove(&id),
+        };
+    }
+
+    /// Recompute the held keys from every active touch and press or release
+    /// the ones that changed.
+    pub fn update(&mut self, keyboard: &mut KeyboardInput, to_ndc: impl Fn(Vec2) -> Vec2) {
+        let now_held: HashSet<Key> = self
+            .touches
+            .values()
+            .filter_map(|px| region(to_ndc(*px)))
+            .collect();
+        for key in self.held.difference(&now_held) {
+            keyboard.set_key(*key, false);
+        }
+        for key in now_held.difference(&self.held) {
+            keyboard.set_key(*key, true);
+        }
+        self.held = now_held;
+    }
+
+    /// Release everything, e.g. when the controls are switched off.
+    pub fn clear(&mut self, keyboard: &mut KeyboardInput) {
+        self.touches.clear();
+        for key in self.held.drain() {
+            keyboard.set_key(key, false);
+        }
+    }
+
+    pub fn draw(&self, out: &mut Vec<DrawCmd>) {
+        let idle = Rgba(0.5, 0.5, 0.5, 0.4);
+        let lit = Rgba(0.6, 1.0, 0.6, 0.8);
+        let color = |key: Key| if self.held.contains(&key) { lit } else { idle };
+
+        let r = DPAD_RADIUS;
+        let arms = [
+            (Key::Up, Vec2::new(0.0, 1.0)),
+            (Key::Down, Vec2::new(0.0, -1.0)),
+            (Key::Left, Vec2::new(-1.0, 0.0)),
+            (Key::Right, Vec2::new(1.0, 0.0)),
+        ];
+        for (key, dir) in arms {
+            let tip = DPAD_CENTER + dir * r;
+            let side = dir.perp() * r * 0.35;
+            let base = DPAD_CENTER + dir * r * 0.35;
+            overlay::polyline(
+                out,
+                vec![base + side, tip, base - side],
+                true,
+                color(key),
+                2.0,
+            );
+        }
+        let pts = (0..16)
+            .map(|i| {
+                let a = i as f32 / 16.0 * std::f32::consts::TAU;
+                FIRE_CENTER + Vec2::new(a.cos(), a.sin()) * FIRE_RADIUS
+            })
+            .collect();
+        overlay::polyline(out, pts, true, color(Key::Space), 2.0);
+    }
+}
+
+/// The key under an NDC point, if it is on a control.
+fn region(p: Vec2) -> Option<Key> {
+    let d = p - DPAD_CENTER;
+    if d.length() <= DPAD_RADIUS {
+        if d.length() < DPAD_DEAD {
+            return None;
+        }
+        return Some(if d.x.abs() > d.y.abs() {
+            if d.x > 0.0 {
+                Key::Right
+            } else {
+                Key::Left
+            }
+        } else if d.y > 0.0 {
+            Key::Up
+        } else {
+            Key::Down
+        });
+    }
+    (p.distance(FIRE_CENTER) <= FIRE_RADIUS).then_some(Key::Space)
+}
@@ -0,0 +1,93 @@
+//! Developer tuning panel for the glow look (`?dev=1`, toggled with F8).
+//!
+//! The sliders write straight into `GameState::tuning`, which reshapes every
+//! stroke's glow on the next frame, so the look can be dialed in without a
+//! rebuild. Nothing here is saved; copy the numbers you like into code.
+
+use vectorcade_shared::draw::DrawCmd;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::display_list;
+use crate::query;
+use crate::settings_dialog::slider;
+use crate::GAME_STATE;
+
+/// Live glow parameters. The defaults leave strokes untouched.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tuning {
+    /// Added to every glowing stroke's glow.
+    pub glow_base: f32,
+    /// Multiplies every stroke's own glow.
+    pub glow_scale: f32,
+    /// Glow given to strokes that ask for none.
+    pub default_glow: f32,
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Self {
+            glow_base: 0.0,
+            glow_scale: 1.0,
+            default_glow: 0.0,
+        }
+    }
+}
+
+impl Tuning {
+    /// Tuning starts available only with `?dev=1`.
+    pub fn from_url() -> Option<Self> {
+        (query::param("dev").as_deref() == Some("1")).then(Self::default)
+    }
+
+    /// Rewrite the glow of every stroke in `cmds`.
+    pub fn apply(&self, cmds: &mut [DrawCmd]) {
+        if *self == Self::default() {
+            return;
+        }
+        display_list::for_each_stroke(cmds, |stroke| {
+            stroke.glow = if stroke.glow > 0.0 {
+                self.glow_base + stroke.glow * self.glow_scale
+            } else {
+                self.default_glow
+            };
+        });
+    }
+}
+
+#[function_component(TuningPanel)]
+pub fn tuning_panel() -> Html {
+    let tuning = use_state(|| GAME_STATE.with(|state| state.borrow().tuning.unwrap_or_default()));
+
+    // Build an input handler that writes a slider value into one field.
+    let on_slider = |apply: fn(&mut Tuning, f32)| {
+        let tuning = tuning.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut next = *tuning;
+            apply(&mut next, input.value().parse::<f32>().unwrap_or_default());
+            GAME_STATE.with(|state| state.borrow_mut().tuning = Some(next));
+            tuning.set(next);
+        })
+    };
+    let on_defaults = {
+        let tuning = tuning.clone();
+        Callback::from(move |_: MouseEvent| {
+            GAME_STATE.with(|state| state.borrow_mut().tuning = Some(Tuning::default()));
+            tuning.set(Tuning::default());
+        })
+    };
+
+    html! {
+        <div class="tuning-panel">
+            <h3>{ "Tuning (F8)" }</h3>
+            { slider("Glow base", 0.0, 4.0, 0.05, tuning.glow_base,
+                on_slider(|t, v| t.glow_base = v)) }
+            { slider("Glow scale", 0.0, 4.0, 0.05, tuning.glow_scale,
+                on_slider(|t, v| t.glow_scale = v)) }
+            { slider("Default glow", 0.0, 4.0, 0.05, tuning.default_glow,
+                on_slider(|t, v| t.default_glow = v)) }
+            <button onclick={on_defaults}>{ "Defaults" }</button>
+        </div>
+    }
+}
@@ -0,0 +1,115 @@
+//! Platform-owned view effects: screen shake and zoom.
+//!
+//! Shake and zoom are applied to the whole frame after the game renders, so
+//! games get juice effects without jittering their own geometry. Requests come
+//! in through the `request_shake` / `request_zoom` JS hooks.
+
+use glam::Vec2;
+use vectorcade_shared::draw::DrawCmd;
+use wasm_bindgen::prelude::*;
+
+use crate::{display_list, GAME_STATE};
+
+/// How fast zoom eases toward its target, per second.
+const ZOOM_EASE: f32 = 8.0;
+/// Shake is scaled by this when the user prefers reduced motion.
+const REDUCED_MOTION_SHAKE: f32 = 0.25;
+
+pub struct ViewFx {
+    shake_intensity: f32,
+    shake_until: f64,
+    shake_duration: f64,
+    zoom: f32,
+    zoom_target: f32,
+    last: f64,
+    reduced_motion: bool,
+}
+
+impl Default for ViewFx {
+    fn default() -> Self {
+        Self {
+            shake_intensity: 0.0,
+            shake_until: 0.0,
+            shake_duration: 0.0,
+            zoom: 1.0,
+            zoom_target: 1.0,
+            last: 0.0,
+            reduced_motion: prefers_reduced_motion(),
+        }
+    }
+}
+
+impl ViewFx {
+    /// Shake the view by up to `intensity` NDC units, decaying over `duration_ms`.
+    pub fn request_shake(&mut self, intensity: f32, duration_ms: f64) {
+        self.shake_intensity = self.shake_intensity.max(intensity);
+        self.shake_duration = duration_ms.max(1.0);
+        self.shake_until = self.last + self.shake_duration;
+    }
+
+    /// Ease the view toward `factor` zoom around the origin.
+    pub fn request_zoom(&mut self, factor: f32) {
+        self.zoom_target = factor.clamp(0.1, 10.0);
+    }
+
+    /// Advance the effects to `now` and apply them to the frame.
+    pub fn apply(&mut self, now: f64, cmds: &mut [DrawCmd]) {
+        let dt = if self.last > 0.0 {
+            ((now - self.last) / 1000.0) as f32
+        } else {
+            0.0
+        };
+        self.last = now;
+        self.zoom += (self.zoom_target - self.zoom) * (ZOOM_EASE * dt).min(1.0);
+
+        let remaining = ((self.shake_until - now) / self.shake_duration).max(0.0) as f32;
+        if remaining <= 0.0 {
+            self.shake_intensity = 0.0;
+        }
+        let mut amplitude = self.shake_intensity * remaining;
+        if self.reduced_motion {
+            amplitude *= REDUCED_MOTION_SHAKE;
+        }
+
+        if amplitude <= 0.0 && (self.zoom - 1.0).abs() < 1e-4 {
+            return;
+        }
+        let offset = Vec2::new(jitter(now, 1.0), jitter(now, 2.0)) * amplitude;
+        let zoom = self.zoom;
+        display_list::map_points(cmds, |p| p * zoom + offset);
+    }
+}
+
+/// Pseudo-random value in [-1, 1] derived from the frame time.
+fn jitter(now: f64, salt: f64) -> f32 {
+    let x = ((now * 12.9898 + salt * 78.233).sin() * 43_758.545).fract();
+    (x * 2.0 - 1.0) as f32
+}
+
+/// Whether the user asked the OS for reduced motion.
+pub fn prefers_reduced_motion() -> bool {
+    web_sys::window()
+        .and_then(|w| {
+            w.match_media("(prefers-reduced-motion: reduce)")
+                .ok()
+                .flatten()
+        })
+        .is_some_and(|m| m.matches())
+}
+
+/// Shake the view; intended for host pages and future game hooks.
+#[wasm_bindgen]
+pub fn request_shake(intensity: f32, duration_ms: f64) {
+    GAME_STATE.with(|state| {
+        state
+            .borrow_mut()
+            .view
+            .request_shake(intensity, duration_ms)
+    });
+}
+
+/// Zoom the view around the origin.
+#[wasm_bindgen]
+pub fn request_zoom(factor: f32) {
+    GAME_STATE.with(|state| state.borrow_mut().view.request_zoom(factor));
+}
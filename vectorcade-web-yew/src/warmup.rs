@@ -0,0 +1,38 @@
+//! Optional start-up warm-up (Settings → Display).
+//!
+//! A game's first reset and frames allocate its buffers, which shows as a
+//! hitch the first time it is picked. Once the first game has been running
+//! for `START_DELAY_MS`, each other game is run headless for `TICKS` steps,
+//! one game per frame, so later switches are smooth.
+
+/// Delay after the first frame before warming starts (ms).
+const START_DELAY_MS: f64 = 1000.0;
+/// Fixed steps run per game.
+pub const TICKS: usize = 10;
+/// RNG seed for warm-up runs; any nonzero value will do.
+pub const SEED: u64 = 1;
+
+#[derive(Default)]
+pub struct WarmUp {
+    started_at: Option<f64>,
+    next: usize,
+}
+
+impl WarmUp {
+    /// Index of the game to warm at `now` (ms), skipping `selected`, or
+    /// `None` until the start delay has passed and once all are done.
+    pub fn next_game(&mut self, now: f64, count: usize, selected: usize) -> Option<usize> {
+        let started_at = *self.started_at.get_or_insert(now);
+        if now - started_at < START_DELAY_MS {
+            return None;
+        }
+        if self.next == selected {
+            self.next += 1;
+        }
+        if self.next >= count {
+            return None;
+        }
+        self.next += 1;
+        Some(self.next - 1)
+    }
+}